@@ -1,7 +1,12 @@
+use crate::parser::{StrawberryValue, TailCall};
+
 #[derive(Debug)]
 pub enum StrawberryErrorKind {
     SyntaxError(String),
-    SemanticError(String)
+    SemanticError(String),
+    StackOverflow(usize, Vec<String>),
+    Return(Box<StrawberryValue>),
+    TailCall(Box<TailCall>)
 }
 
 #[derive(Debug)]
@@ -20,4 +25,58 @@ impl StrawberryError {
             kind: StrawberryErrorKind::SemanticError(message.to_string())
         }
     }
+    pub fn stack_overflow(max_call_depth: usize, call_chain: Vec<String>) -> Self {
+        Self {
+            kind: StrawberryErrorKind::StackOverflow(max_call_depth, call_chain)
+        }
+    }
+    pub fn return_value(value: StrawberryValue) -> Self {
+        Self {
+            kind: StrawberryErrorKind::Return(Box::new(value))
+        }
+    }
+    pub fn tail_call(call: TailCall) -> Self {
+        Self {
+            kind: StrawberryErrorKind::TailCall(Box::new(call))
+        }
+    }
+}
+
+const MAX_CYCLE_LENGTH: usize = 8;
+const CALL_CHAIN_EDGE: usize = 10;
+
+pub fn format_call_chain(call_chain: &[String]) -> String {
+    let mut frames: Vec<String> = Vec::new();
+    let mut position = 0;
+    while position < call_chain.len() {
+        let remaining = &call_chain[position..];
+        let mut cycle = (1, 1);
+        for length in 1..=MAX_CYCLE_LENGTH.min(remaining.len() / 2) {
+            let pattern = &remaining[..length];
+            let count = remaining.chunks_exact(length).take_while(|chunk| *chunk == pattern).count();
+            if count > 1 {
+                cycle = (length, count);
+                break;
+            }
+        }
+
+        let (length, count) = cycle;
+        let names = remaining[..length].join(" -> ");
+        frames.push(match (length, count) {
+            (_, 1) => names,
+            (1, _) => format!("{names} (x{count})"),
+            _ => format!("({names}) (x{count})")
+        });
+        position += length * count;
+    }
+
+    if frames.len() > CALL_CHAIN_EDGE * 2 {
+        let hidden = frames.len() - CALL_CHAIN_EDGE * 2;
+        let tail = frames.split_off(frames.len() - CALL_CHAIN_EDGE);
+        frames.truncate(CALL_CHAIN_EDGE);
+        frames.push(format!("… ({hidden} more)"));
+        frames.extend(tail);
+    }
+
+    frames.join(" -> ")
 }
\ No newline at end of file
@@ -13,18 +13,10 @@ macro_rules! skip_whitespace {
 macro_rules! high_skip_whitespace {
     ($obj:expr) => {
         while let Some(current_character) = $obj.current_character {
-            if current_character.is_whitespace() {
-                $obj.next_character();
+            if !current_character.is_whitespace() {
                 break;
             }
-        }
-    };
-}
-
-macro_rules! treat_strawberry_error {
-    ($val:expr, $err:ident,$msg:expr) => {
-        if let Err(_) = $val {
-            return Err(StrawberryError::$err($msg));
+            $obj.next_character();
         }
     };
 }
@@ -53,17 +45,61 @@ pub enum TokenKind {
     LiteralString(String),
     BracketScope(Vec<Token>),
     Identifier(String),
-    Let(String, Option<Box<Token>>),
+    Let(Pattern, Option<Box<Token>>),
+    Const(Pattern, Box<Token>),
     Call(String, Vec<Token>),
     Number(f64),
     Boolean(bool),
     Comparison(ComparisonKind,Box<Token>, Box<Token>),
     Attribution,
     Expression(ExpressionKind, Box<Token>, Box<Token>),
-    Function(String, Vec<String>, Box<Token>),
+    Function(String, Vec<Parameter>, Box<Token>),
+    Return(Option<Box<Token>>),
+    List(Vec<Token>),
+    Index(Box<Token>, Box<Token>),
+    Map(Vec<(String, Token)>),
+    Member(Box<Token>, String),
+    Struct(String, Vec<String>),
+    Class(String, Vec<String>, Vec<Token>),
+    MethodCall(Box<Token>, String, Vec<Token>),
+    Enum(String, Vec<(String, Vec<String>)>),
+    Match(Box<Token>, Vec<(Pattern, Token)>),
+    Assignment(Box<Token>, Box<Token>),
+    Nil,
+    OptionalMember(Box<Token>, String),
+    OptionalMethodCall(Box<Token>, String, Vec<Token>),
+    Coalesce(Box<Token>, Box<Token>),
+    Conditional(Box<Token>, Box<Token>, Box<Token>),
+    NamedArgument(String, Box<Token>),
+    Import(ModulePath, String),
+    ImportNames(ModulePath, Vec<String>),
+    Export(Box<Token>),
     Unknown
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pattern {
+    Wildcard,
+    Binding(String),
+    Literal(Box<Token>),
+    Variant(String, Vec<Pattern>),
+    List(Vec<Pattern>, Option<String>),
+    Fields(Vec<(String, Pattern)>)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ModulePath {
+    File(String),
+    Standard(String)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Parameter {
+    pub pattern: Pattern,
+    pub default: Option<Box<Token>>,
+    pub variadic: bool
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct TokenSpan {
     pub start: usize,
@@ -95,7 +131,7 @@ impl <'a> StrawberryLexer <'a> {
             character_stream: source.chars(),
             current_character: Some(char::default()),
             index: -1,
-            operators: &[ '=', '!', '<', '>', '+', '-', '*', '/' ]
+            operators: &[ '=', '!', '<', '>', '+', '-', '*', '/', '?' ]
         }
     }
 
@@ -107,6 +143,15 @@ impl <'a> StrawberryLexer <'a> {
     fn peek_character(&self) -> Option<char> {
         self.source.chars().nth(self.index as usize)
     }
+
+    fn peek_next_character(&self) -> Option<char> {
+        self.source.chars().nth(self.index as usize + 1)
+    }
+
+    // Spans count characters, not bytes, so the source cannot be sliced directly
+    fn source_text(&self, start: usize, end: usize) -> String {
+        self.source.chars().skip(start).take(end - start).collect()
+    }
     
 
     fn parse_multiline_string(&mut self) -> Result<Token, StrawberryError> {
@@ -134,7 +179,7 @@ impl <'a> StrawberryLexer <'a> {
         let span = TokenSpan {
             start,
             end,
-            text: self.source[start..end].to_string()
+            text: self.source_text(start, end)
         };
         let token = Token {
             kind: TokenKind::LiteralString(string_text),
@@ -160,7 +205,7 @@ impl <'a> StrawberryLexer <'a> {
 
         let message = "Missing \"'\" at the end of the string";
         if !matches!(self.current_character, Some('\'')) {
-            return Err(StrawberryError::syntax_error(&message));
+            return Err(StrawberryError::syntax_error(message));
         }
 
         self.next_character();
@@ -170,7 +215,7 @@ impl <'a> StrawberryLexer <'a> {
         let span = TokenSpan {
             start,
             end,
-            text: self.source[start..end].to_string()
+            text: self.source_text(start, end)
         };
         let token = Token {
             kind: TokenKind::LiteralString(string_text),
@@ -180,48 +225,51 @@ impl <'a> StrawberryLexer <'a> {
         Ok(token)
     }
 
-    fn parse_bracket_scope(&mut self) -> Result<Token, StrawberryError> {
-        let start = self.index as usize;
-        let mut scope_tokens = Vec::new();
-        let mut index = 1usize;
-        self.next_character();
+    // Operators pop their left operand from `self.tokens`, so nested code is read into a fresh list
+    fn parse_until(&mut self, terminators: &[char], separators: &[char]) -> Result<Vec<Token>, StrawberryError> {
+        let outer_tokens = std::mem::take(&mut self.tokens);
+        let mut result = Ok(());
 
         while let Some(current_character) = self.current_character {
-            if [ '}' ].contains(&current_character) {
+            if terminators.contains(&current_character) {
                 break;
             }
-
+            if separators.contains(&current_character) {
+                self.next_character();
+                continue;
+            }
             skip_whitespace!(current_character, self);
-            let last_token = self.next_token();
-            if let Ok(next_token_binding) = last_token {
-                index += 1;
-                self.tokens.push(next_token_binding);
-            } else {
-                break;
+            match self.next_token() {
+                Ok(token) => self.tokens.push(token),
+                Err(error) => {
+                    result = Err(error);
+                    break;
+                }
             }
         }
 
-        if !matches!(self.current_character, Some('}')) {
-            return Err(StrawberryError::syntax_error("Scope was not closed"));
-        }
+        let inner_tokens = std::mem::replace(&mut self.tokens, outer_tokens);
+        result.map(|_| inner_tokens)
+    }
 
+    fn parse_bracket_scope(&mut self) -> Result<Token, StrawberryError> {
+        let start = self.index as usize;
         self.next_character();
 
-        for _ in 1..index {
-            let last_token = self.tokens.pop();
-            if let Some(token) = last_token {
-                scope_tokens.push(token);
-            }
+        let scope_tokens = self.parse_until(&[ '}' ], &[])?;
+
+        if !matches!(self.current_character, Some('}')) {
+            return Err(StrawberryError::syntax_error("Scope was not closed"));
         }
 
-        scope_tokens.reverse();
+        self.next_character();
 
         let end = self.index as usize;
 
         let span = TokenSpan {
             start,
             end,
-            text: self.source[start..end].to_string()
+            text: self.source_text(start, end)
         };
 
         let token = Token {
@@ -234,136 +282,373 @@ impl <'a> StrawberryLexer <'a> {
 
     fn parse_symbol(&mut self) -> Result<Token, StrawberryError> {
         let start = self.index as usize;
-        let mut symbol_name = String::new();
-
-        while let Some(current_character) = self.current_character {
-            if !current_character.is_alphanumeric() && current_character != '_' {
-                break;
-            }
-            symbol_name.push(current_character);
-            self.next_character();
-        }
+        let symbol_name = self.parse_name();
 
         let mut token_kind = TokenKind::Identifier(symbol_name.clone());
 
         if symbol_name == "let" {
             let mut variable_value = None;
             high_skip_whitespace!(self);
-            let variable_name = self.next_token();
-            treat_strawberry_error!(variable_name, syntax_error, "Set a variable name at the \"let\" statement");
-            let variable_name_binding = variable_name.unwrap();
-            if let TokenKind::Identifier(variable_name) = variable_name_binding.kind {
-                high_skip_whitespace!(self);
-                let operator_token_binding = self.next_token();
-    
-                if let Ok(operator_token) = operator_token_binding {
-                    if operator_token.kind == TokenKind::Attribution {
-                        while let Some(current_char) = self.current_character {
-                            skip_whitespace!(current_char, self);
-                            if current_char == ';' {
-                                break;
-                            }
-                            let variable_value_binding = self.next_token()?;
-                            self.tokens.push(variable_value_binding);
-                        }
-                        if self.current_character != Some(';') {
-                            return Err(StrawberryError::syntax_error("Let statement was expecting a semicolon"));
-                        }
-                        self.next_character();
-                        let last_token_result = self.tokens.pop();
+            let pattern = self.parse_pattern()?;
+            high_skip_whitespace!(self);
 
-                        if let Some(last_token) = last_token_result {
-                            variable_value = Some(Box::new(last_token));
-                        } else {
-                            return Err(StrawberryError::syntax_error("Let statement was expecting a value"));
-                        }
-                    }
+            if self.current_character == Some('=') {
+                self.next_character();
+                let mut value_tokens = self.parse_until(&[ ';' ], &[])?;
+                if self.current_character != Some(';') {
+                    return Err(StrawberryError::syntax_error("Let statement was expecting a semicolon"));
                 }
-    
-                token_kind = TokenKind::Let(
-                    variable_name,
-                    variable_value
-                );
+                self.next_character();
+
+                if let Some(last_token) = value_tokens.pop() {
+                    variable_value = Some(Box::new(last_token));
+                } else {
+                    return Err(StrawberryError::syntax_error("Let statement was expecting a value"));
+                }
+            } else if self.current_character == Some(';') {
+                if !matches!(pattern, Pattern::Binding(_)) {
+                    return Err(StrawberryError::syntax_error("Destructuring let statement was expecting a value"));
+                }
+                self.next_character();
             } else {
-                return Err(StrawberryError::syntax_error("Let statement was expecting an identifier."));
+                return Err(StrawberryError::syntax_error("Let statement was expecting a semicolon"));
+            }
+
+            token_kind = TokenKind::Let(
+                pattern,
+                variable_value
+            );
+        }
+
+        if symbol_name == "const" {
+            high_skip_whitespace!(self);
+            let pattern = self.parse_pattern()?;
+            high_skip_whitespace!(self);
+
+            if self.current_character != Some('=') {
+                return Err(StrawberryError::syntax_error("Const statement was expecting a value"));
+            }
+            self.next_character();
+
+            let mut value_tokens = self.parse_until(&[ ';' ], &[])?;
+            if self.current_character != Some(';') {
+                return Err(StrawberryError::syntax_error("Const statement was expecting a semicolon"));
             }
+            self.next_character();
+
+            let value = value_tokens.pop().ok_or_else(|| {
+                StrawberryError::syntax_error("Const statement was expecting a value")
+            })?;
+
+            token_kind = TokenKind::Const(pattern, Box::new(value));
         }
 
         if symbol_name == "function" {
             high_skip_whitespace!(self);
-        
-            let function_name = self.next_token();
-            treat_strawberry_error!(
-                function_name,
-                syntax_error,
-                "Expected a function name after 'function'"
-            );
 
-            let function_binding = function_name.unwrap();
-            let function_data = if let TokenKind::Call(name, call_arguments) = function_binding.kind {
-                let arguments: Vec<String> = call_arguments.iter().map(|arg| {
-                    let argument_name = if let TokenKind::Identifier(arg_name) = arg.clone().kind {
-                        arg_name
-                    } else {
-                        String::new()
-                    };
-                    argument_name
-                }).collect();
-                (name, arguments)
-            } else {
+            let function_name = self.parse_name();
+            if function_name.is_empty() {
+                return Err(StrawberryError::syntax_error("Expected a function name after 'function'"));
+            }
+
+            if self.current_character != Some('(') {
                 return Err(StrawberryError::syntax_error(
                     "Malformed function",
                 ));
-            };
-        
+            }
+            self.next_character();
+            let arguments = self.parse_parameter_list(&function_name)?;
+
             high_skip_whitespace!(self);
             if self.current_character != Some('{') {
                 return Err(StrawberryError::syntax_error(
                     "Expected '{' to start the function body.",
                 ));
             }
-        
+
             let function_body = self.parse_bracket_scope()?;
-        
-            token_kind = TokenKind::Function(function_data.0, function_data.1, Box::new(function_body));
+
+            token_kind = TokenKind::Function(function_name, arguments, Box::new(function_body));
+        }
+
+        if symbol_name == "struct" {
+            high_skip_whitespace!(self);
+            let struct_name = self.parse_name();
+            if struct_name.is_empty() {
+                return Err(StrawberryError::syntax_error("Expected a struct name after 'struct'"));
+            }
+
+            high_skip_whitespace!(self);
+            if self.current_character != Some('{') {
+                return Err(StrawberryError::syntax_error("Expected '{' to start the struct fields."));
+            }
+            self.next_character();
+
+            let mut fields = Vec::new();
+            while let Some(current_character) = self.current_character {
+                if current_character == '}' {
+                    break;
+                }
+
+                if current_character == ',' {
+                    self.next_character();
+                    continue;
+                }
+
+                skip_whitespace!(current_character, self);
+
+                let field = self.parse_name();
+                if field.is_empty() {
+                    return Err(StrawberryError::syntax_error(&format!("Unexpected character in struct: \"{current_character}\"")));
+                }
+                if fields.contains(&field) {
+                    return Err(StrawberryError::syntax_error(&format!("Duplicate field '{field}' in struct {struct_name}")));
+                }
+                fields.push(field);
+            }
+
+            if self.current_character != Some('}') {
+                return Err(StrawberryError::syntax_error("Struct was not closed"));
+            }
+            self.next_character();
+
+            token_kind = TokenKind::Struct(struct_name, fields);
+        }
+
+        if symbol_name == "class" {
+            high_skip_whitespace!(self);
+            let class_name = self.parse_name();
+            if class_name.is_empty() {
+                return Err(StrawberryError::syntax_error("Expected a class name after 'class'"));
+            }
+
+            high_skip_whitespace!(self);
+            if self.current_character != Some('{') {
+                return Err(StrawberryError::syntax_error("Expected '{' to start the class body."));
+            }
+            self.next_character();
+
+            let body = self.parse_until(&[ '}' ], &[ ',' ])?;
+
+            if self.current_character != Some('}') {
+                return Err(StrawberryError::syntax_error("Class was not closed"));
+            }
+            self.next_character();
+
+            let mut fields = Vec::new();
+            let mut methods = Vec::new();
+            for member in body {
+                match &member.kind {
+                    TokenKind::Identifier(field) if !fields.contains(field) => fields.push(field.clone()),
+                    TokenKind::Function(_, _, _) => methods.push(member),
+                    _ => return Err(StrawberryError::syntax_error(&format!(
+                        "Unexpected \"{}\" in class {class_name}; classes can only declare fields and methods",
+                        member.span.text
+                    )))
+                }
+            }
+
+            token_kind = TokenKind::Class(class_name, fields, methods);
+        }
+
+        if symbol_name == "enum" {
+            high_skip_whitespace!(self);
+            let enum_name = self.parse_name();
+            if enum_name.is_empty() {
+                return Err(StrawberryError::syntax_error("Expected an enum name after 'enum'"));
+            }
+
+            high_skip_whitespace!(self);
+            if self.current_character != Some('{') {
+                return Err(StrawberryError::syntax_error("Expected '{' to start the enum variants."));
+            }
+            self.next_character();
+
+            let body = self.parse_until(&[ '}' ], &[ ',' ])?;
+
+            if self.current_character != Some('}') {
+                return Err(StrawberryError::syntax_error("Enum was not closed"));
+            }
+            self.next_character();
+
+            let mut variants: Vec<(String, Vec<String>)> = Vec::new();
+            for variant in body {
+                let (variant_name, fields) = match &variant.kind {
+                    TokenKind::Identifier(name) => (name.clone(), Vec::new()),
+                    TokenKind::Call(name, arguments) if arguments.iter().all(|a| matches!(a.kind, TokenKind::Identifier(_))) => {
+                        let fields = arguments.iter().filter_map(|argument| match &argument.kind {
+                            TokenKind::Identifier(field) => Some(field.clone()),
+                            _ => None
+                        }).collect();
+                        (name.clone(), fields)
+                    },
+                    _ => return Err(StrawberryError::syntax_error(&format!(
+                        "Unexpected \"{}\" in enum {enum_name}",
+                        variant.span.text
+                    )))
+                };
+
+                if variants.iter().any(|(name, _)| *name == variant_name) {
+                    return Err(StrawberryError::syntax_error(&format!("Duplicate variant '{variant_name}' in enum {enum_name}")));
+                }
+                variants.push((variant_name, fields));
+            }
+
+            token_kind = TokenKind::Enum(enum_name, variants);
+        }
+
+        if symbol_name == "match" {
+            let mut value_tokens = self.parse_until(&[ '{' ], &[])?;
+            let value = value_tokens.pop().ok_or_else(|| {
+                StrawberryError::syntax_error("Expected a value after 'match'")
+            })?;
+
+            if self.current_character != Some('{') {
+                return Err(StrawberryError::syntax_error("Expected '{' to start the match arms."));
+            }
+            self.next_character();
+
+            let mut arms = Vec::new();
+            while let Some(current_character) = self.current_character {
+                if current_character == '}' {
+                    break;
+                }
+
+                if current_character == ',' {
+                    self.next_character();
+                    continue;
+                }
+
+                skip_whitespace!(current_character, self);
+
+                let pattern = self.parse_pattern()?;
+
+                high_skip_whitespace!(self);
+                if self.current_character != Some('=') || self.peek_next_character() != Some('>') {
+                    return Err(StrawberryError::syntax_error("Expected '=>' after the match pattern"));
+                }
+                self.next_character();
+                self.next_character();
+                high_skip_whitespace!(self);
+
+                let body = if self.current_character == Some('{') && !self.is_map_literal() {
+                    self.parse_bracket_scope()?
+                } else {
+                    let mut body_tokens = self.parse_until(&[ ',', '}', '\n' ], &[])?;
+                    body_tokens.pop().ok_or_else(|| {
+                        StrawberryError::syntax_error("Expected a value after '=>'")
+                    })?
+                };
+
+                arms.push((pattern, body));
+            }
+
+            if self.current_character != Some('}') {
+                return Err(StrawberryError::syntax_error("Match was not closed"));
+            }
+            self.next_character();
+
+            token_kind = TokenKind::Match(Box::new(value), arms);
+        }
+
+        if symbol_name == "import" {
+            let path = self.parse_module_path()?;
+            high_skip_whitespace!(self);
+
+            let alias = match &path {
+                ModulePath::Standard(name) if !self.next_word_is("as") => name.clone(),
+                _ => {
+                    if self.parse_name() != "as" {
+                        return Err(StrawberryError::syntax_error("Import was expecting 'as' followed by a name"));
+                    }
+                    high_skip_whitespace!(self);
+
+                    let alias = self.parse_name();
+                    if alias.is_empty() {
+                        return Err(StrawberryError::syntax_error("Expected a module name after 'as'"));
+                    }
+                    alias
+                }
+            };
+
+            token_kind = TokenKind::Import(path, alias);
+        }
+
+        if symbol_name == "from" {
+            let path = self.parse_module_path()?;
+            high_skip_whitespace!(self);
+
+            if self.parse_name() != "import" {
+                return Err(StrawberryError::syntax_error("Expected 'import' after the module path"));
+            }
+
+            let mut names = Vec::new();
+            loop {
+                high_skip_whitespace!(self);
+                let name = self.parse_name();
+                if name.is_empty() {
+                    return Err(StrawberryError::syntax_error("Expected a name to import"));
+                }
+                names.push(name);
+
+                high_skip_whitespace!(self);
+                if self.current_character != Some(',') {
+                    break;
+                }
+                self.next_character();
+            }
+
+            token_kind = TokenKind::ImportNames(path, names);
+        }
+
+        if symbol_name == "export" {
+            high_skip_whitespace!(self);
+            let declaration = self.next_token()?;
+
+            match declaration.kind {
+                TokenKind::Function(_, _, _)
+                | TokenKind::Let(_, _)
+                | TokenKind::Const(_, _)
+                | TokenKind::Struct(_, _)
+                | TokenKind::Class(_, _, _)
+                | TokenKind::Enum(_, _) => token_kind = TokenKind::Export(Box::new(declaration)),
+                _ => return Err(StrawberryError::syntax_error("Only declarations can be exported")),
+            }
+        }
+
+        if symbol_name == "return" {
+            let mut return_value = None;
+            high_skip_whitespace!(self);
+
+            if self.current_character != Some(';') {
+                let mut value_tokens = self.parse_until(&[ ';' ], &[])?;
+                if self.current_character != Some(';') {
+                    return Err(StrawberryError::syntax_error("Return statement was expecting a semicolon"));
+                }
+                if value_tokens.len() > 1 {
+                    return Err(StrawberryError::syntax_error("Return statement was expecting a single value"));
+                }
+                return_value = value_tokens.pop().map(Box::new);
+            }
+
+            self.next_character();
+            token_kind = TokenKind::Return(return_value);
         }
 
         if [ "true", "false" ].contains(&symbol_name.as_str()) {
             token_kind = TokenKind::Boolean(symbol_name == "true")
         }
 
+        if symbol_name == "nil" {
+            token_kind = TokenKind::Nil
+        }
+
         if let TokenKind::Identifier(function_name) = token_kind.clone() {
             let peek = self.peek_character();
             if let Some(peeked) = peek {
                 if peeked == '(' {
-                    let mut arguments = Vec::new();
-                    let mut index = 1usize;
                     self.next_character();
-                    while let Some(current_character) = self.current_character {
-                        if current_character == ')' {
-                            break;
-                        }
-
-                        if current_character == ',' || current_character.is_whitespace() {
-                            self.next_character();
-                            continue;
-                        }
-                        
-                        let next_token = self.next_token();
-
-                        if let Ok(next_token_binding) = next_token {
-                            index += 1;
-                            match &next_token_binding.kind {
-                                TokenKind::Expression(_, _, _) => {
-                                    index -= 1;
-                                },
-                                _ => ()
-                            };
-                            self.tokens.push(next_token_binding);
-                        } else {
-                            break;
-                        }
-                    }
+                    let arguments = self.parse_arguments()?;
 
                     if self.current_character != Some(')') {
                         return Err(StrawberryError::syntax_error("Function call was not closed"));
@@ -371,15 +656,6 @@ impl <'a> StrawberryLexer <'a> {
 
                     self.next_character();
 
-                    for _ in 1..index {
-                        let last_token = self.tokens.pop();
-                        if let Some(token) = last_token {
-                            arguments.push(token);
-                        }
-                    }
-
-                    arguments.reverse();
-
                     token_kind = TokenKind::Call(function_name, arguments);
                 }
             }
@@ -389,7 +665,7 @@ impl <'a> StrawberryLexer <'a> {
             let span = TokenSpan {
                 start,
                 end,
-                text: self.source[start..end].to_string()
+                text: self.source_text(start, end)
             };
 
         let token = Token {
@@ -397,9 +673,501 @@ impl <'a> StrawberryLexer <'a> {
             span
         };
 
+        if matches!(token.kind, TokenKind::Identifier(_) | TokenKind::Call(_, _) | TokenKind::Nil) {
+            return self.parse_postfix(token);
+        }
+
         Ok(token)
     }
 
+    fn parse_list(&mut self) -> Result<Token, StrawberryError> {
+        let start = self.index as usize;
+        self.next_character();
+
+        let elements = self.parse_until(&[ ']' ], &[ ',' ])?;
+
+        if self.current_character != Some(']') {
+            return Err(StrawberryError::syntax_error("List was not closed"));
+        }
+
+        self.next_character();
+
+        let end = self.index as usize;
+        let span = TokenSpan {
+            start,
+            end,
+            text: self.source_text(start, end)
+        };
+
+        let token = Token {
+            kind: TokenKind::List(elements),
+            span
+        };
+
+        self.parse_postfix(token)
+    }
+
+    fn is_map_literal(&self) -> bool {
+        let mut characters = self.source.chars().skip(self.index as usize + 1).skip_while(|c| c.is_whitespace()).peekable();
+
+        match characters.next() {
+            Some('}') => return true,
+            Some('\'') => {
+                if !characters.any(|c| c == '\'') {
+                    return false;
+                }
+            },
+            Some(c) if c.is_alphabetic() || c == '_' => {
+                while characters.next_if(|c| c.is_alphanumeric() || *c == '_').is_some() {}
+            },
+            _ => return false
+        }
+
+        characters.find(|c| !c.is_whitespace()) == Some(':')
+    }
+
+    fn parse_map(&mut self) -> Result<Token, StrawberryError> {
+        let start = self.index as usize;
+        let mut entries = Vec::new();
+        self.next_character();
+
+        while let Some(current_character) = self.current_character {
+            if current_character == '}' {
+                break;
+            }
+
+            if current_character == ',' {
+                self.next_character();
+                continue;
+            }
+
+            skip_whitespace!(current_character, self);
+
+            let key = if current_character == '\'' {
+                match self.parse_literal_string()?.kind {
+                    TokenKind::LiteralString(key) => key,
+                    _ => unreachable!()
+                }
+            } else {
+                self.parse_name()
+            };
+
+            if key.is_empty() {
+                return Err(StrawberryError::syntax_error(&format!("Unexpected character in map: \"{current_character}\"")));
+            }
+
+            high_skip_whitespace!(self);
+            if self.current_character != Some(':') {
+                return Err(StrawberryError::syntax_error(&format!("Expected ':' after the map key '{key}'")));
+            }
+            self.next_character();
+
+            let mut value_tokens = self.parse_until(&[ ',', '}' ], &[])?;
+            let value = value_tokens.pop().ok_or_else(|| {
+                StrawberryError::syntax_error(&format!("Map key '{key}' was expecting a value"))
+            })?;
+
+            entries.push((key, value));
+        }
+
+        if self.current_character != Some('}') {
+            return Err(StrawberryError::syntax_error("Map was not closed"));
+        }
+
+        self.next_character();
+
+        let end = self.index as usize;
+        let span = TokenSpan {
+            start,
+            end,
+            text: self.source_text(start, end)
+        };
+
+        let token = Token {
+            kind: TokenKind::Map(entries),
+            span
+        };
+
+        self.parse_postfix(token)
+    }
+
+    fn parse_pattern(&mut self) -> Result<Pattern, StrawberryError> {
+        high_skip_whitespace!(self);
+
+        match self.current_character {
+            Some('_') if !self.peek_next_character().is_some_and(|c| c.is_alphanumeric() || c == '_') => {
+                self.next_character();
+                Ok(Pattern::Wildcard)
+            },
+            Some('\'') => Ok(Pattern::Literal(Box::new(self.parse_literal_string()?))),
+            Some('`') => Ok(Pattern::Literal(Box::new(self.parse_multiline_string()?))),
+            Some('-') => {
+                let start = self.index as usize;
+                self.next_character();
+                let mut number = self.parse_number()?;
+                if let TokenKind::Number(value) = number.kind {
+                    number.kind = TokenKind::Number(-value);
+                }
+                number.span.start = start;
+                Ok(Pattern::Literal(Box::new(number)))
+            },
+            Some(c) if c.is_ascii_digit() => Ok(Pattern::Literal(Box::new(self.parse_number()?))),
+            Some(c) if c.is_alphabetic() || c == '_' => {
+                let start = self.index as usize;
+                let name = self.parse_name();
+
+                if name == "true" || name == "false" || name == "nil" {
+                    let end = self.index as usize;
+                    let kind = if name == "nil" {
+                        TokenKind::Nil
+                    } else {
+                        TokenKind::Boolean(name == "true")
+                    };
+                    return Ok(Pattern::Literal(Box::new(Token {
+                        kind,
+                        span: TokenSpan {
+                            start,
+                            end,
+                            text: self.source_text(start, end)
+                        }
+                    })));
+                }
+
+                if self.current_character != Some('(') {
+                    return Ok(Pattern::Binding(name));
+                }
+                self.next_character();
+
+                let fields = self.parse_pattern_list(')')?;
+                Ok(Pattern::Variant(name, fields))
+            },
+            Some('[') => {
+                self.next_character();
+                let mut elements = Vec::new();
+                let mut rest = None;
+
+                loop {
+                    high_skip_whitespace!(self);
+                    match self.current_character {
+                        Some(']') => break,
+                        Some(',') => self.next_character(),
+                        Some('.') if rest.is_none() && self.source.chars().skip(self.index as usize).take(3).eq("...".chars()) => {
+                            for _ in 0..3 {
+                                self.next_character();
+                            }
+                            let name = self.parse_name();
+                            if name.is_empty() {
+                                return Err(StrawberryError::syntax_error("Expected a name after '...'"));
+                            }
+                            rest = Some(name);
+                        },
+                        Some(_) if rest.is_some() => {
+                            return Err(StrawberryError::syntax_error("The '...' rest pattern must be the last element"));
+                        },
+                        Some(_) => elements.push(self.parse_pattern()?),
+                        None => return Err(StrawberryError::syntax_error("Pattern was not closed"))
+                    }
+                }
+                self.next_character();
+
+                Ok(Pattern::List(elements, rest))
+            },
+            Some('{') => {
+                self.next_character();
+                let mut fields = Vec::new();
+
+                loop {
+                    high_skip_whitespace!(self);
+                    match self.current_character {
+                        Some('}') => break,
+                        Some(',') => self.next_character(),
+                        Some(c) if c.is_alphabetic() || c == '_' => {
+                            let field = self.parse_name();
+                            high_skip_whitespace!(self);
+                            let field_pattern = if self.current_character == Some(':') {
+                                self.next_character();
+                                self.parse_pattern()?
+                            } else {
+                                Pattern::Binding(field.clone())
+                            };
+                            fields.push((field, field_pattern));
+                        },
+                        Some(c) => return Err(StrawberryError::syntax_error(&format!("Unexpected character in pattern: \"{c}\""))),
+                        None => return Err(StrawberryError::syntax_error("Pattern was not closed"))
+                    }
+                }
+                self.next_character();
+
+                Ok(Pattern::Fields(fields))
+            },
+            Some(c) => Err(StrawberryError::syntax_error(&format!("Unexpected character in pattern: \"{c}\""))),
+            None => Err(StrawberryError::syntax_error("Unexpected EOF."))
+        }
+    }
+
+    fn parse_pattern_list(&mut self, closing: char) -> Result<Vec<Pattern>, StrawberryError> {
+        let mut patterns = Vec::new();
+
+        loop {
+            high_skip_whitespace!(self);
+            match self.current_character {
+                Some(c) if c == closing => break,
+                Some(',') => self.next_character(),
+                Some(_) => patterns.push(self.parse_pattern()?),
+                None => return Err(StrawberryError::syntax_error("Pattern was not closed"))
+            }
+        }
+        self.next_character();
+
+        Ok(patterns)
+    }
+
+    fn parse_parameter_list(&mut self, function_name: &str) -> Result<Vec<Parameter>, StrawberryError> {
+        let mut parameters: Vec<Parameter> = Vec::new();
+
+        loop {
+            high_skip_whitespace!(self);
+            match self.current_character {
+                Some(')') => break,
+                Some(',') => self.next_character(),
+                Some(_) if parameters.last().is_some_and(|parameter| parameter.variadic) => {
+                    return Err(StrawberryError::syntax_error(&format!(
+                        "The '...' parameter must be the last parameter of {function_name}"
+                    )));
+                },
+                Some('.') if self.source.chars().skip(self.index as usize).take(3).eq("...".chars()) => {
+                    for _ in 0..3 {
+                        self.next_character();
+                    }
+                    let name = self.parse_name();
+                    if name.is_empty() {
+                        return Err(StrawberryError::syntax_error("Expected a name after '...'"));
+                    }
+                    parameters.push(Parameter {
+                        pattern: Pattern::Binding(name),
+                        default: None,
+                        variadic: true
+                    });
+                },
+                Some(_) => {
+                    let pattern = self.parse_pattern()?;
+                    high_skip_whitespace!(self);
+
+                    let mut default = None;
+                    if self.current_character == Some('=') {
+                        self.next_character();
+                        let mut default_tokens = self.parse_until(&[ ',', ')' ], &[])?;
+                        let value = default_tokens.pop().ok_or_else(|| {
+                            StrawberryError::syntax_error("Default parameter value was expecting a value")
+                        })?;
+                        default = Some(Box::new(value));
+                    } else if parameters.last().is_some_and(|parameter| parameter.default.is_some()) {
+                        return Err(StrawberryError::syntax_error(&format!(
+                            "Parameters without a default value cannot follow parameters with one in {function_name}"
+                        )));
+                    }
+
+                    parameters.push(Parameter {
+                        pattern,
+                        default,
+                        variadic: false
+                    });
+                },
+                None => return Err(StrawberryError::syntax_error("Parameter list was not closed"))
+            }
+        }
+        self.next_character();
+
+        Ok(parameters)
+    }
+
+    fn is_named_argument(&self) -> bool {
+        let mut characters = self.source.chars().skip(self.index as usize).peekable();
+
+        if !characters.peek().is_some_and(|c| c.is_alphabetic() || *c == '_') {
+            return false;
+        }
+
+        let mut characters = characters.skip_while(|c| c.is_alphanumeric() || *c == '_').skip_while(|c| c.is_whitespace());
+        characters.next() == Some(':')
+    }
+
+    fn parse_arguments(&mut self) -> Result<Vec<Token>, StrawberryError> {
+        let mut arguments = Vec::new();
+
+        loop {
+            high_skip_whitespace!(self);
+            match self.current_character {
+                Some(')') | None => break,
+                Some(',') => self.next_character(),
+                Some(_) if self.is_named_argument() => {
+                    let start = self.index as usize;
+                    let name = self.parse_name();
+                    high_skip_whitespace!(self);
+                    self.next_character();
+
+                    let mut value_tokens = self.parse_until(&[ ',', ')' ], &[])?;
+                    let value = value_tokens.pop().ok_or_else(|| {
+                        StrawberryError::syntax_error(&format!("Named argument '{name}' was expecting a value"))
+                    })?;
+
+                    let end = self.index as usize;
+                    arguments.push(Token {
+                        kind: TokenKind::NamedArgument(name, Box::new(value)),
+                        span: TokenSpan {
+                            start,
+                            end,
+                            text: self.source_text(start, end)
+                        }
+                    });
+                },
+                Some(_) => arguments.extend(self.parse_until(&[ ',', ')' ], &[])?)
+            }
+        }
+
+        Ok(arguments)
+    }
+
+    fn parse_module_path(&mut self) -> Result<ModulePath, StrawberryError> {
+        high_skip_whitespace!(self);
+        if self.current_character != Some('\'') {
+            let name = self.parse_name();
+            if name.is_empty() {
+                return Err(StrawberryError::syntax_error("Expected a module name or a path in quotes"));
+            }
+            return Ok(ModulePath::Standard(name));
+        }
+
+        match self.parse_literal_string()?.kind {
+            TokenKind::LiteralString(path) => Ok(ModulePath::File(path)),
+            _ => Err(StrawberryError::syntax_error("Expected a module path in quotes")),
+        }
+    }
+
+    fn next_word_is(&self, word: &str) -> bool {
+        let mut characters = self.source.chars().skip(self.index as usize);
+        characters.by_ref().take(word.chars().count()).eq(word.chars())
+            && !characters.next().is_some_and(|c| c.is_alphanumeric() || c == '_')
+    }
+
+    fn parse_name(&mut self) -> String {
+        let mut name = String::new();
+
+        while let Some(current_character) = self.current_character {
+            if !current_character.is_alphanumeric() && current_character != '_' {
+                break;
+            }
+            name.push(current_character);
+            self.next_character();
+        }
+
+        name
+    }
+
+    fn parse_postfix(&mut self, mut token: Token) -> Result<Token, StrawberryError> {
+        let start = token.span.start;
+
+        loop {
+            let kind = match self.current_character {
+                Some('[') => {
+                    self.next_character();
+                    let mut index_tokens = self.parse_until(&[ ']' ], &[])?;
+
+                    if self.current_character != Some(']') {
+                        return Err(StrawberryError::syntax_error("Index was not closed"));
+                    }
+
+                    self.next_character();
+
+                    let index = index_tokens.pop().ok_or_else(|| {
+                        StrawberryError::syntax_error("Index was expecting a value")
+                    })?;
+
+                    TokenKind::Index(Box::new(token), Box::new(index))
+                },
+                Some('.') | Some('?') => {
+                    let optional = self.current_character == Some('?');
+                    if optional {
+                        if self.peek_next_character() != Some('.') {
+                            break;
+                        }
+                        self.next_character();
+                    }
+
+                    self.next_character();
+                    let name = self.parse_name();
+
+                    if name.is_empty() {
+                        return Err(StrawberryError::syntax_error("Expected a field name after '.'"));
+                    }
+
+                    if self.current_character == Some('(') {
+                        self.next_character();
+                        let arguments = self.parse_arguments()?;
+
+                        if self.current_character != Some(')') {
+                            return Err(StrawberryError::syntax_error("Method call was not closed"));
+                        }
+
+                        self.next_character();
+                        if optional {
+                            TokenKind::OptionalMethodCall(Box::new(token), name, arguments)
+                        } else {
+                            TokenKind::MethodCall(Box::new(token), name, arguments)
+                        }
+                    } else if optional {
+                        TokenKind::OptionalMember(Box::new(token), name)
+                    } else {
+                        TokenKind::Member(Box::new(token), name)
+                    }
+                },
+                _ => break
+            };
+
+            let end = self.index as usize;
+            token = Token {
+                kind,
+                span: TokenSpan {
+                    start,
+                    end,
+                    text: self.source_text(start, end)
+                }
+            };
+        }
+
+        Ok(token)
+    }
+
+    fn parse_conditional_branch(&mut self) -> Result<Token, StrawberryError> {
+        let outer_tokens = std::mem::take(&mut self.tokens);
+        let mut result = Ok(());
+
+        high_skip_whitespace!(self);
+        match self.next_token() {
+            Ok(token) => self.tokens.push(token),
+            Err(error) => result = Err(error)
+        }
+
+        while result.is_ok() {
+            high_skip_whitespace!(self);
+            match self.current_character {
+                Some(current_character) if self.operators.contains(&current_character) => {
+                    match self.next_token() {
+                        Ok(token) => self.tokens.push(token),
+                        Err(error) => result = Err(error)
+                    }
+                },
+                _ => break
+            }
+        }
+
+        let mut branch_tokens = std::mem::replace(&mut self.tokens, outer_tokens);
+        result?;
+        branch_tokens.pop().ok_or_else(|| {
+            StrawberryError::syntax_error("Conditional expression was expecting a value after ':'")
+        })
+    }
+
     fn parse_operator(&mut self) -> Result<Token, StrawberryError> {
         let mut start = self.index as usize;
         let mut operator = String::new();
@@ -417,6 +1185,22 @@ impl <'a> StrawberryLexer <'a> {
 
         if operator == "=" {
             token_kind = TokenKind::Attribution;
+
+            if let Some(Token { kind: TokenKind::Index(_, _) | TokenKind::Member(_, _), .. }) = self.tokens.last() {
+                let target = self.tokens.pop().unwrap();
+                let mut value_tokens = self.parse_until(&[ ';' ], &[])?;
+                if self.current_character != Some(';') {
+                    return Err(StrawberryError::syntax_error("Assignment was expecting a semicolon"));
+                }
+                self.next_character();
+
+                let value = value_tokens.pop().ok_or_else(|| {
+                    StrawberryError::syntax_error("Assignment was expecting a value")
+                })?;
+
+                start = target.span.start;
+                token_kind = TokenKind::Assignment(Box::new(target), Box::new(value));
+            }
         }
 
         if operator == "+" {
@@ -504,13 +1288,56 @@ impl <'a> StrawberryLexer <'a> {
             if is_unary {
                 let unary_number = next_token.unwrap();
                 if let TokenKind::Number(number) = unary_number.kind {
-                    token_kind = TokenKind::Number(number * -1.0);
+                    token_kind = TokenKind::Number(-number);
                 } else {
                     return Err(StrawberryError::syntax_error("The unary operator can be used only on numbers"));
                 }
             }
         }
 
+        if operator == "?" {
+            let condition = self.tokens.pop().ok_or_else(|| {
+                StrawberryError::syntax_error("Conditional expression was expecting a condition before '?'")
+            })?;
+
+            let mut then_tokens = self.parse_until(&[ ':' ], &[])?;
+            if self.current_character != Some(':') {
+                return Err(StrawberryError::syntax_error("Conditional expression was expecting ':'"));
+            }
+            self.next_character();
+
+            let then_branch = then_tokens.pop().ok_or_else(|| {
+                StrawberryError::syntax_error("Conditional expression was expecting a value before ':'")
+            })?;
+            let else_branch = self.parse_conditional_branch()?;
+
+            start = condition.span.start;
+            token_kind = TokenKind::Conditional(
+                Box::new(condition),
+                Box::new(then_branch),
+                Box::new(else_branch),
+            );
+        }
+
+        if operator == "??" {
+            let last_token = self.tokens.pop();
+            while let Some(current_character) = self.current_character {
+                skip_whitespace!(current_character, self);
+                break;
+            }
+            let next_token = self.next_token();
+
+            if let Some(left_operand) = last_token {
+                if let Ok(right_operand) = next_token {
+                    start -= (left_operand.span.end - left_operand.span.start) + 1;
+                    token_kind = TokenKind::Coalesce(
+                        Box::new(left_operand),
+                        Box::new(right_operand),
+                    )
+                }
+            }
+        }
+
         if operator == "==" {
             let last_token = self.tokens.pop();
             while let Some(current_character) = self.current_character {
@@ -639,7 +1466,7 @@ impl <'a> StrawberryLexer <'a> {
         let span = TokenSpan {
             start,
             end,
-            text: self.source[start..end].to_string()
+            text: self.source_text(start, end)
         };
 
         let token = Token {
@@ -656,7 +1483,7 @@ impl <'a> StrawberryLexer <'a> {
         let mut is_float = false;
     
         while let Some(current_character) = self.current_character {
-            if current_character.is_digit(10) {
+            if current_character.is_ascii_digit() {
                 number_str.push(current_character);
             } else if current_character == '.' {
                 if is_float {
@@ -678,7 +1505,7 @@ impl <'a> StrawberryLexer <'a> {
         let span = TokenSpan {
             start,
             end,
-            text: self.source[start..end].to_string(),
+            text: self.source_text(start, end),
         };
         let token = Token {
             kind: TokenKind::Number(number),
@@ -691,32 +1518,39 @@ impl <'a> StrawberryLexer <'a> {
     fn next_token(&mut self) -> Result<Token, StrawberryError> {
         if let Some(current_character) = self.current_character {
             if current_character == '\'' {
-                return Ok(self.parse_literal_string()?);
+                return self.parse_literal_string();
             }
     
             if current_character == '`' {
-                return Ok(self.parse_multiline_string()?);
+                return self.parse_multiline_string();
             }
     
             if current_character == '{' {
-                return Ok(self.parse_bracket_scope()?);
+                if self.is_map_literal() {
+                    return self.parse_map();
+                }
+                return self.parse_bracket_scope();
+            }
+
+            if current_character == '[' {
+                return self.parse_list();
             }
 
             if self.operators.contains(&current_character) {
-                return Ok(self.parse_operator()?);
+                return self.parse_operator();
             }
 
-            if current_character.is_digit(10) {
-                return Ok(self.parse_number()?);
+            if current_character.is_ascii_digit() {
+                return self.parse_number();
             }
 
             if current_character.is_alphabetic() {
-                return Ok(self.parse_symbol()?);
+                return self.parse_symbol();
             }
 
-            return Err(StrawberryError::syntax_error(&format!("Unexpected character: \"{}\"", current_character)));
+            Err(StrawberryError::syntax_error(&format!("Unexpected character: \"{}\"", current_character)))
         } else {
-            return Err(StrawberryError::syntax_error("Unexpected EOF."));
+            Err(StrawberryError::syntax_error("Unexpected EOF."))
         }
     }
 
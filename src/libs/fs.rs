@@ -0,0 +1,119 @@
+use std::{cell::RefCell, fs, io, path::{Path, PathBuf}, rc::Rc};
+
+use crate::{error::StrawberryError, parser::{StrawberryParser, StrawberryValue}};
+
+use super::expect_arguments;
+
+fn expect_path(args: &[StrawberryValue], function_name: &str, parser: &StrawberryParser) -> Result<PathBuf, StrawberryError> {
+    if let StrawberryValue::String(path) = &args[0] {
+        let script_directory = parser.module_scope.file_path.parent().unwrap_or(Path::new(""));
+        Ok(script_directory.join(path))
+    } else {
+        Err(StrawberryError::semantic_error(&format!(
+            "First argument of '{function_name}' must be a path string"
+        )))
+    }
+}
+
+fn expect_contents<'a>(args: &'a [StrawberryValue], function_name: &str) -> Result<&'a str, StrawberryError> {
+    if let StrawberryValue::String(contents) = &args[1] {
+        Ok(contents)
+    } else {
+        Err(StrawberryError::semantic_error(&format!(
+            "Second argument of '{function_name}' must be a string"
+        )))
+    }
+}
+
+fn fs_error(action: &str, path: &Path, error: io::Error) -> StrawberryError {
+    StrawberryError::semantic_error(&format!("Could not {action} '{}': {error}", path.display()))
+}
+
+pub fn read_file(args: Vec<StrawberryValue>, parser: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 1, "read_file")?;
+    let path = expect_path(&args, "read_file", parser)?;
+
+    let contents = fs::read_to_string(&path).map_err(|error| fs_error("read", &path, error))?;
+    Ok(StrawberryValue::String(contents))
+}
+
+pub fn write_file(args: Vec<StrawberryValue>, parser: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 2, "write_file")?;
+    let path = expect_path(&args, "write_file", parser)?;
+    let contents = expect_contents(&args, "write_file")?;
+
+    fs::write(&path, contents).map_err(|error| fs_error("write", &path, error))?;
+    Ok(StrawberryValue::Empty)
+}
+
+pub fn append_file(args: Vec<StrawberryValue>, parser: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 2, "append_file")?;
+    let path = expect_path(&args, "append_file", parser)?;
+    let contents = expect_contents(&args, "append_file")?;
+
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .map_err(|error| fs_error("open", &path, error))?;
+    io::Write::write_all(&mut file, contents.as_bytes()).map_err(|error| fs_error("append to", &path, error))?;
+    Ok(StrawberryValue::Empty)
+}
+
+pub fn exists(args: Vec<StrawberryValue>, parser: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 1, "exists")?;
+    let path = expect_path(&args, "exists", parser)?;
+    Ok(StrawberryValue::Boolean(path.exists()))
+}
+
+pub fn list_dir(args: Vec<StrawberryValue>, parser: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 1, "list_dir")?;
+    let path = expect_path(&args, "list_dir", parser)?;
+
+    let mut names = Vec::new();
+    for entry in fs::read_dir(&path).map_err(|error| fs_error("list", &path, error))? {
+        let entry = entry.map_err(|error| fs_error("list", &path, error))?;
+        names.push(entry.file_name().to_string_lossy().into_owned());
+    }
+    names.sort();
+
+    let names = names.into_iter().map(StrawberryValue::String).collect();
+    Ok(StrawberryValue::List(Rc::new(RefCell::new(names))))
+}
+
+pub fn mkdir(args: Vec<StrawberryValue>, parser: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 1, "mkdir")?;
+    let path = expect_path(&args, "mkdir", parser)?;
+
+    fs::create_dir_all(&path).map_err(|error| fs_error("create the directory", &path, error))?;
+    Ok(StrawberryValue::Empty)
+}
+
+pub fn remove(args: Vec<StrawberryValue>, parser: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 1, "remove")?;
+    let path = expect_path(&args, "remove", parser)?;
+
+    let result = if path.is_dir() {
+        fs::remove_dir(&path)
+    } else {
+        fs::remove_file(&path)
+    };
+    result.map_err(|error| fs_error("remove", &path, error))?;
+    Ok(StrawberryValue::Empty)
+}
+
+pub fn join(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    if args.is_empty() {
+        return Err(StrawberryError::semantic_error("'join' expected at least one path"));
+    }
+
+    let mut path = PathBuf::new();
+    for arg in &args {
+        match arg {
+            StrawberryValue::String(part) => path.push(part),
+            _ => return Err(StrawberryError::semantic_error("Arguments of 'join' must be strings")),
+        }
+    }
+
+    Ok(StrawberryValue::String(path.to_string_lossy().into_owned()))
+}
\ No newline at end of file
@@ -0,0 +1,52 @@
+use std::io::{self, BufRead, Read, Write};
+
+use crate::{error::StrawberryError, parser::{StrawberryParser, StrawberryValue}};
+
+use super::expect_arguments;
+
+fn io_error(function_name: &str, error: io::Error) -> StrawberryError {
+    StrawberryError::semantic_error(&format!("'{function_name}' could not read the input: {error}"))
+}
+
+pub fn input(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    if args.len() > 1 {
+        return Err(StrawberryError::semantic_error(&format!(
+            "'input' expected at most 1 argument, but got {}",
+            args.len()
+        )));
+    }
+
+    if let Some(prompt) = args.first() {
+        print!("{prompt}");
+        io::stdout().flush().map_err(|error| io_error("input", error))?;
+    }
+
+    let mut line = String::new();
+    let read = io::stdin().lock().read_line(&mut line).map_err(|error| io_error("input", error))?;
+
+    if read == 0 {
+        return Ok(StrawberryValue::Nil);
+    }
+
+    if line.ends_with('\n') {
+        line.pop();
+        if line.ends_with('\r') {
+            line.pop();
+        }
+    }
+
+    Ok(StrawberryValue::String(line))
+}
+
+pub fn read_all(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 0, "read_all")?;
+
+    let mut text = String::new();
+    let read = io::stdin().lock().read_to_string(&mut text).map_err(|error| io_error("read_all", error))?;
+
+    if read == 0 {
+        return Ok(StrawberryValue::Nil);
+    }
+
+    Ok(StrawberryValue::String(text))
+}
\ No newline at end of file
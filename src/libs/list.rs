@@ -0,0 +1,70 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{error::StrawberryError, parser::{list_position, StrawberryParser, StrawberryValue}};
+
+use super::expect_arguments;
+
+type List = Rc<RefCell<Vec<StrawberryValue>>>;
+
+fn expect_list(value: &StrawberryValue, function_name: &str) -> Result<List, StrawberryError> {
+    if let StrawberryValue::List(list) = value {
+        Ok(list.clone())
+    } else {
+        Err(StrawberryError::semantic_error(&format!(
+            "First argument of '{function_name}' must be a list"
+        )))
+    }
+}
+
+pub fn len(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 1, "len")?;
+
+    match &args[0] {
+        StrawberryValue::List(list) => Ok(StrawberryValue::Number(list.borrow().len() as f64)),
+        StrawberryValue::Map(map) => Ok(StrawberryValue::Number(map.borrow().len() as f64)),
+        StrawberryValue::String(string) => Ok(StrawberryValue::Number(string.chars().count() as f64)),
+        _ => Err(StrawberryError::semantic_error("Argument of 'len' must be a list, a map or a string"))
+    }
+}
+
+pub fn push(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 2, "push")?;
+    let list = expect_list(&args[0], "push")?;
+
+    list.borrow_mut().push(args[1].clone());
+    Ok(StrawberryValue::Empty)
+}
+
+pub fn pop(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 1, "pop")?;
+    let list = expect_list(&args[0], "pop")?;
+
+    let value = list.borrow_mut().pop();
+    value.ok_or_else(|| StrawberryError::semantic_error("Cannot pop from an empty list"))
+}
+
+pub fn insert(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 3, "insert")?;
+    let list = expect_list(&args[0], "insert")?;
+
+    let mut list = list.borrow_mut();
+    let length = list.len();
+    // Inserting right after the last element is allowed, like appending
+    let position = if matches!(args[1], StrawberryValue::Number(number) if number == length as f64) {
+        length
+    } else {
+        list_position(&args[1], length)?
+    };
+
+    list.insert(position, args[2].clone());
+    Ok(StrawberryValue::Empty)
+}
+
+pub fn remove(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 2, "remove")?;
+    let list = expect_list(&args[0], "remove")?;
+
+    let mut list = list.borrow_mut();
+    let position = list_position(&args[1], list.len())?;
+    Ok(list.remove(position))
+}
\ No newline at end of file
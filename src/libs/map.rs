@@ -0,0 +1,70 @@
+use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
+
+use crate::{error::StrawberryError, parser::{StrawberryParser, StrawberryValue}};
+
+use super::expect_arguments;
+
+type Map = Rc<RefCell<BTreeMap<String, StrawberryValue>>>;
+
+fn expect_map(args: &[StrawberryValue], count: usize, function_name: &str) -> Result<Map, StrawberryError> {
+    expect_arguments(args, count, function_name)?;
+
+    if let StrawberryValue::Map(map) = &args[0] {
+        Ok(map.clone())
+    } else {
+        Err(StrawberryError::semantic_error(&format!(
+            "First argument of '{function_name}' must be a map"
+        )))
+    }
+}
+
+fn expect_key<'a>(value: &'a StrawberryValue, function_name: &str) -> Result<&'a str, StrawberryError> {
+    if let StrawberryValue::String(key) = value {
+        Ok(key)
+    } else {
+        Err(StrawberryError::semantic_error(&format!(
+            "Second argument of '{function_name}' must be a string"
+        )))
+    }
+}
+
+fn to_list(values: Vec<StrawberryValue>) -> StrawberryValue {
+    StrawberryValue::List(Rc::new(RefCell::new(values)))
+}
+
+pub fn len(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    let map = expect_map(&args, 1, "len")?;
+
+    let len = map.borrow().len();
+    Ok(StrawberryValue::Number(len as f64))
+}
+
+pub fn has(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    let map = expect_map(&args, 2, "has")?;
+    let key = expect_key(&args[1], "has")?;
+
+    let contains_key = map.borrow().contains_key(key);
+    Ok(StrawberryValue::Boolean(contains_key))
+}
+
+pub fn keys(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    let map = expect_map(&args, 1, "keys")?;
+
+    let keys = map.borrow().keys().cloned().map(StrawberryValue::String).collect();
+    Ok(to_list(keys))
+}
+
+pub fn values(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    let map = expect_map(&args, 1, "values")?;
+
+    let values = map.borrow().values().cloned().collect();
+    Ok(to_list(values))
+}
+
+pub fn delete(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    let map = expect_map(&args, 2, "delete")?;
+    let key = expect_key(&args[1], "delete")?;
+
+    let removed = map.borrow_mut().remove(key);
+    Ok(StrawberryValue::Boolean(removed.is_some()))
+}
\ No newline at end of file
@@ -0,0 +1,138 @@
+use crate::{error::StrawberryError, parser::{StrawberryParser, StrawberryValue}};
+
+use super::expect_arguments;
+
+fn expect_numbers(args: &[StrawberryValue], count: usize, function_name: &str) -> Result<Vec<f64>, StrawberryError> {
+    expect_arguments(args, count, function_name)?;
+
+    args.iter().map(|arg| match arg {
+        StrawberryValue::Number(number) => Ok(*number),
+        _ => Err(StrawberryError::semantic_error(&format!(
+            "Arguments of '{function_name}' must be numbers, but got a {}",
+            arg.type_name()
+        )))
+    }).collect()
+}
+
+fn unary(args: Vec<StrawberryValue>, function_name: &str, operation: fn(f64) -> f64) -> Result<StrawberryValue, StrawberryError> {
+    let numbers = expect_numbers(&args, 1, function_name)?;
+    Ok(StrawberryValue::Number(operation(numbers[0])))
+}
+
+pub fn sqrt(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    let numbers = expect_numbers(&args, 1, "sqrt")?;
+    if numbers[0] < 0.0 {
+        return Err(StrawberryError::semantic_error(&format!("Cannot take the square root of the negative number {}", numbers[0])));
+    }
+    Ok(StrawberryValue::Number(numbers[0].sqrt()))
+}
+
+pub fn pow(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    let numbers = expect_numbers(&args, 2, "pow")?;
+    Ok(StrawberryValue::Number(numbers[0].powf(numbers[1])))
+}
+
+pub fn abs(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    unary(args, "abs", f64::abs)
+}
+
+pub fn floor(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    unary(args, "floor", f64::floor)
+}
+
+pub fn ceil(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    unary(args, "ceil", f64::ceil)
+}
+
+pub fn round(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    unary(args, "round", f64::round)
+}
+
+fn extreme(args: Vec<StrawberryValue>, function_name: &str, pick: fn(f64, f64) -> f64) -> Result<StrawberryValue, StrawberryError> {
+    let values = match args.as_slice() {
+        [StrawberryValue::List(list)] => list.borrow().clone(),
+        _ => args,
+    };
+
+    if values.is_empty() {
+        return Err(StrawberryError::semantic_error(&format!("'{function_name}' expected at least one number")));
+    }
+
+    let numbers = expect_numbers(&values, values.len(), function_name)?;
+    Ok(StrawberryValue::Number(numbers.into_iter().reduce(pick).unwrap_or_default()))
+}
+
+pub fn min(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    extreme(args, "min", f64::min)
+}
+
+pub fn max(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    extreme(args, "max", f64::max)
+}
+
+pub fn clamp(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    let numbers = expect_numbers(&args, 3, "clamp")?;
+    if numbers[1].is_nan() || numbers[2].is_nan() {
+        return Err(StrawberryError::semantic_error("Bounds of 'clamp' must not be NaN"));
+    }
+    if numbers[1] > numbers[2] {
+        return Err(StrawberryError::semantic_error(&format!(
+            "Lower bound {} of 'clamp' is greater than the upper bound {}",
+            numbers[1], numbers[2]
+        )));
+    }
+    Ok(StrawberryValue::Number(numbers[0].clamp(numbers[1], numbers[2])))
+}
+
+pub fn sin(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    unary(args, "sin", f64::sin)
+}
+
+pub fn cos(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    unary(args, "cos", f64::cos)
+}
+
+pub fn tan(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    unary(args, "tan", f64::tan)
+}
+
+pub fn asin(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    unary(args, "asin", f64::asin)
+}
+
+pub fn acos(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    unary(args, "acos", f64::acos)
+}
+
+pub fn atan(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    unary(args, "atan", f64::atan)
+}
+
+pub fn atan2(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    let numbers = expect_numbers(&args, 2, "atan2")?;
+    Ok(StrawberryValue::Number(numbers[0].atan2(numbers[1])))
+}
+
+pub fn log(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    let numbers = if args.len() == 2 {
+        expect_numbers(&args, 2, "log")?
+    } else {
+        expect_numbers(&args, 1, "log")?
+    };
+
+    if numbers[0] <= 0.0 {
+        return Err(StrawberryError::semantic_error(&format!("Cannot take the logarithm of {}", numbers[0])));
+    }
+
+    match numbers.get(1) {
+        Some(base) if *base <= 0.0 || *base == 1.0 => {
+            Err(StrawberryError::semantic_error(&format!("Invalid logarithm base {base}")))
+        },
+        Some(base) => Ok(StrawberryValue::Number(numbers[0].log(*base))),
+        None => Ok(StrawberryValue::Number(numbers[0].ln()))
+    }
+}
+
+pub fn exp(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    unary(args, "exp", f64::exp)
+}
\ No newline at end of file
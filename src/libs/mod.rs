@@ -1,22 +1,154 @@
+mod fs;
+mod io;
+mod list;
+mod map;
+mod math;
+mod os;
+mod random;
 mod standard;
+mod string;
+mod time;
+mod types;
 
-use crate::parser::{StrawberryParser, StrawberryValue};
+use crate::{error::StrawberryError, module::Module, parser::{StrawberryParser, StrawberryValue}};
+
+type NativeFunction = fn(Vec<StrawberryValue>, &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError>;
+
+const ORDINALS: [&str; 4] = [ "First", "Second", "Third", "Fourth" ];
+
+fn expect_arguments(args: &[StrawberryValue], count: usize, function_name: &str) -> Result<(), StrawberryError> {
+    if args.len() != count {
+        return Err(StrawberryError::semantic_error(&format!(
+            "'{function_name}' expected {count} arguments, but got {}",
+            args.len()
+        )));
+    }
+    Ok(())
+}
+
+fn expect_integer(args: &[StrawberryValue], position: usize, function_name: &str) -> Result<i64, StrawberryError> {
+    match &args[position] {
+        StrawberryValue::Number(number) if number.fract() == 0.0 => Ok(*number as i64),
+        _ => Err(StrawberryError::semantic_error(&format!(
+            "{} argument of '{function_name}' must be an integer",
+            ORDINALS[position]
+        )))
+    }
+}
+
+fn native(name: &str, function: NativeFunction) -> StrawberryValue {
+    StrawberryValue::NativeFunction(name.into(), function)
+}
 
 pub fn load_standard(parser: &mut StrawberryParser) {
-    parser.variables.insert(
-        "strawberry".into(),
+    parser.define_constant(
+        "strawberry",
         StrawberryValue::NativeFunction("Strawberry".into(), standard::strawberry),
     );
-    parser.variables.insert(
-        "fields_forever".into(),
-        StrawberryValue::String(standard::fields_forever()),
-    );
-    parser.variables.insert(
-        "beatle".into(),
-        StrawberryValue::String(standard::beatle()),
-    );
-    parser.variables.insert( // :P
-        "if".into(),
+    parser.define_dynamic("fields_forever", standard::fields_forever);
+    parser.define_dynamic("beatle", standard::beatle);
+    parser.define_constant( // :P
+        "if",
         StrawberryValue::NativeFunction("IfStatement".into(), standard::if_comparison),
     );
+}
+
+pub fn standard_module(name: &str) -> Option<Module> {
+    let exports = match name {
+        "fs" => vec![
+            ("read_file", native("ReadFile", fs::read_file)),
+            ("write_file", native("WriteFile", fs::write_file)),
+            ("append_file", native("AppendFile", fs::append_file)),
+            ("exists", native("Exists", fs::exists)),
+            ("list_dir", native("ListDir", fs::list_dir)),
+            ("mkdir", native("Mkdir", fs::mkdir)),
+            ("remove", native("Remove", fs::remove)),
+            ("join", native("Join", fs::join)),
+        ],
+        "io" => vec![
+            ("input", native("Input", io::input)),
+            ("read_all", native("ReadAll", io::read_all)),
+        ],
+        "list" => vec![
+            ("len", native("Len", list::len)),
+            ("push", native("Push", list::push)),
+            ("pop", native("Pop", list::pop)),
+            ("insert", native("Insert", list::insert)),
+            ("remove", native("Remove", list::remove)),
+        ],
+        "map" => vec![
+            ("len", native("Len", map::len)),
+            ("has", native("Has", map::has)),
+            ("keys", native("Keys", map::keys)),
+            ("values", native("Values", map::values)),
+            ("delete", native("Delete", map::delete)),
+        ],
+        "math" => vec![
+            ("PI", StrawberryValue::Number(std::f64::consts::PI)),
+            ("E", StrawberryValue::Number(std::f64::consts::E)),
+            ("sqrt", native("Sqrt", math::sqrt)),
+            ("pow", native("Pow", math::pow)),
+            ("abs", native("Abs", math::abs)),
+            ("floor", native("Floor", math::floor)),
+            ("ceil", native("Ceil", math::ceil)),
+            ("round", native("Round", math::round)),
+            ("min", native("Min", math::min)),
+            ("max", native("Max", math::max)),
+            ("clamp", native("Clamp", math::clamp)),
+            ("sin", native("Sin", math::sin)),
+            ("cos", native("Cos", math::cos)),
+            ("tan", native("Tan", math::tan)),
+            ("asin", native("Asin", math::asin)),
+            ("acos", native("Acos", math::acos)),
+            ("atan", native("Atan", math::atan)),
+            ("atan2", native("Atan2", math::atan2)),
+            ("log", native("Log", math::log)),
+            ("exp", native("Exp", math::exp)),
+        ],
+        "os" => vec![
+            ("env", native("Env", os::env)),
+            ("platform", native("Platform", os::platform)),
+            ("exit", native("Exit", os::exit)),
+        ],
+        "random" => vec![
+            ("seed", native("Seed", random::seed)),
+            ("int", native("Int", random::int)),
+            ("float", native("Float", random::float)),
+            ("choice", native("Choice", random::choice)),
+            ("shuffle", native("Shuffle", random::shuffle)),
+        ],
+        "string" => vec![
+            ("len", native("Len", string::len)),
+            ("upper", native("Upper", string::upper)),
+            ("lower", native("Lower", string::lower)),
+            ("trim", native("Trim", string::trim)),
+            ("split", native("Split", string::split)),
+            ("join", native("Join", string::join)),
+            ("replace", native("Replace", string::replace)),
+            ("contains", native("Contains", string::contains)),
+            ("starts_with", native("StartsWith", string::starts_with)),
+            ("ends_with", native("EndsWith", string::ends_with)),
+            ("find", native("Find", string::find)),
+            ("repeat", native("Repeat", string::repeat)),
+            ("pad_left", native("PadLeft", string::pad_left)),
+            ("pad_right", native("PadRight", string::pad_right)),
+            ("substr", native("Substr", string::substr)),
+        ],
+        "time" => vec![
+            ("now", native("Now", time::now)),
+            ("sleep", native("Sleep", time::sleep)),
+        ],
+        "types" => vec![
+            ("type_of", native("TypeOf", types::type_of)),
+            ("to_string", native("ToString", types::to_string)),
+            ("to_number", native("ToNumber", types::to_number)),
+            ("to_bool", native("ToBool", types::to_bool)),
+        ],
+        _ => return None,
+    };
+
+    Some(Module {
+        name: name.to_string(),
+        exports: exports.into_iter().map(|(name, value)| (name.to_string(), value)).collect(),
+    })
 }
\ No newline at end of file
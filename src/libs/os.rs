@@ -0,0 +1,30 @@
+use crate::{error::StrawberryError, parser::{StrawberryParser, StrawberryValue}};
+
+use super::expect_arguments;
+
+pub fn env(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 1, "env")?;
+
+    if let StrawberryValue::String(name) = &args[0] {
+        match std::env::var(name) {
+            Ok(value) => Ok(StrawberryValue::String(value)),
+            Err(_) => Ok(StrawberryValue::Nil)
+        }
+    } else {
+        Err(StrawberryError::semantic_error("Argument of 'env' must be a string"))
+    }
+}
+
+pub fn platform(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 0, "platform")?;
+    Ok(StrawberryValue::String(std::env::consts::OS.to_string()))
+}
+
+pub fn exit(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 1, "exit")?;
+
+    match &args[0] {
+        StrawberryValue::Number(code) if code.fract() == 0.0 => std::process::exit(*code as i32),
+        _ => Err(StrawberryError::semantic_error("Argument of 'exit' must be an integer"))
+    }
+}
\ No newline at end of file
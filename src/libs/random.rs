@@ -0,0 +1,56 @@
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use crate::{error::StrawberryError, parser::{StrawberryParser, StrawberryValue}};
+
+use super::{expect_arguments, expect_integer};
+
+pub fn seed(args: Vec<StrawberryValue>, parser: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 1, "seed")?;
+    let seed = expect_integer(&args, 0, "seed")?;
+
+    *parser.rng.borrow_mut() = StdRng::seed_from_u64(seed as u64);
+    Ok(StrawberryValue::Empty)
+}
+
+pub fn int(args: Vec<StrawberryValue>, parser: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 2, "int")?;
+    let low = expect_integer(&args, 0, "int")?;
+    let high = expect_integer(&args, 1, "int")?;
+
+    if low > high {
+        return Err(StrawberryError::semantic_error(&format!(
+            "Lower bound {low} of 'int' is greater than the upper bound {high}"
+        )));
+    }
+
+    let number = parser.rng.borrow_mut().gen_range(low..=high);
+    Ok(StrawberryValue::Number(number as f64))
+}
+
+pub fn float(args: Vec<StrawberryValue>, parser: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 0, "float")?;
+    Ok(StrawberryValue::Number(parser.rng.borrow_mut().gen::<f64>()))
+}
+
+pub fn choice(args: Vec<StrawberryValue>, parser: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 1, "choice")?;
+
+    if let StrawberryValue::List(list) = &args[0] {
+        let list = list.borrow();
+        let chosen = list.choose(&mut *parser.rng.borrow_mut()).cloned();
+        chosen.ok_or_else(|| StrawberryError::semantic_error("Cannot choose from an empty list"))
+    } else {
+        Err(StrawberryError::semantic_error("Argument of 'choice' must be a list"))
+    }
+}
+
+pub fn shuffle(args: Vec<StrawberryValue>, parser: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 1, "shuffle")?;
+
+    if let StrawberryValue::List(list) = &args[0] {
+        list.borrow_mut().shuffle(&mut *parser.rng.borrow_mut());
+        Ok(StrawberryValue::Empty)
+    } else {
+        Err(StrawberryError::semantic_error("Argument of 'shuffle' must be a list"))
+    }
+}
\ No newline at end of file
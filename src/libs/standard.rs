@@ -2,23 +2,12 @@ use crate::{error::StrawberryError, parser::{StrawberryParser, StrawberryValue}}
 use rand::Rng;
 
 pub fn strawberry(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
-    let mut string_to_print = Vec::new();
-    for arg in args {
-        match arg {
-            StrawberryValue::String(string) => string_to_print.push(string),
-            StrawberryValue::Number(number) => string_to_print.push(number.to_string()),
-            StrawberryValue::NativeFunction(name, _) => string_to_print.push(format!("(Native Function: {})", name)),
-            StrawberryValue::Function(name, _,_) => string_to_print.push(format!("(Function: {})", name)),
-            StrawberryValue::Boolean(boolean) => string_to_print.push(format!("{boolean}")),
-            StrawberryValue::Block(_) => string_to_print.push(format!("(Code block)")),
-            StrawberryValue::Empty => string_to_print.push("(Empty)".into())
-        };
-    }
+    let string_to_print: Vec<String> = args.iter().map(StrawberryValue::to_string).collect();
     println!("{}", string_to_print.join(" "));
     Ok(StrawberryValue::Empty)
 }
 
-pub fn fields_forever() -> String {
+pub fn fields_forever(parser: &StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
     let lyrics = [
         "Let me take you down",
         "'Cause I'm going to strawberry fields",
@@ -31,11 +20,11 @@ pub fn fields_forever() -> String {
         "It doesn't matter much to me"
     ];
 
-    let random_index = rand::thread_rng().gen_range(0..lyrics.len());
-    lyrics[random_index].to_string()
+    let random_index = parser.rng.borrow_mut().gen_range(0..lyrics.len());
+    Ok(StrawberryValue::String(lyrics[random_index].to_string()))
 }
 
-pub fn beatle() -> String {
+pub fn beatle(parser: &StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
     let lyrics = [
         "Paul McCartney",
         "John Lennon",
@@ -43,15 +32,15 @@ pub fn beatle() -> String {
         "Ringo Starr"
     ];
 
-    let random_index = rand::thread_rng().gen_range(0..lyrics.len());
-    lyrics[random_index].to_string()
+    let random_index = parser.rng.borrow_mut().gen_range(0..lyrics.len());
+    Ok(StrawberryValue::String(lyrics[random_index].to_string()))
 }
 
 pub fn execute_code_block(args: Vec<StrawberryValue>, context: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
-    let arg0 = args.get(0).unwrap();
+    let arg0 = args.first().unwrap();
     let mut result = StrawberryValue::Empty;
     if let StrawberryValue::Block(code) = arg0 {
-        result = StrawberryParser::new(code.iter().map(|t| *t.clone()).collect(), context.variables.clone()).run_token_stream()?;
+        result = context.execute_block(code.clone())?;
     }
     Ok(result)
 }
@@ -61,7 +50,7 @@ pub fn if_comparison(mut args: Vec<StrawberryValue>, context: &mut StrawberryPar
 
     if let StrawberryValue::Boolean(boolean) = condition {
         if boolean {
-            if let Some(if_block) = args.get(0) {
+            if let Some(if_block) = args.first() {
                 return execute_code_block(vec![if_block.clone()], context);
             }
         } else {
@@ -0,0 +1,208 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{error::StrawberryError, parser::{StrawberryParser, StrawberryValue}};
+
+use super::{expect_arguments, expect_integer, ORDINALS};
+
+const MAX_STRING_LENGTH: usize = 1 << 30;
+
+fn expect_string<'a>(args: &'a [StrawberryValue], position: usize, function_name: &str) -> Result<&'a str, StrawberryError> {
+    if let StrawberryValue::String(string) = &args[position] {
+        Ok(string)
+    } else {
+        Err(StrawberryError::semantic_error(&format!(
+            "{} argument of '{function_name}' must be a string",
+            ORDINALS[position]
+        )))
+    }
+}
+
+fn expect_argument_range(args: &[StrawberryValue], min: usize, max: usize, function_name: &str) -> Result<(), StrawberryError> {
+    if args.len() < min || args.len() > max {
+        return Err(StrawberryError::semantic_error(&format!(
+            "'{function_name}' expected {min} to {max} arguments, but got {}",
+            args.len()
+        )));
+    }
+    Ok(())
+}
+
+fn character_position(index: i64, length: usize, function_name: &str) -> Result<usize, StrawberryError> {
+    let position = if index < 0 { length as i64 + index } else { index };
+
+    if position < 0 || position > length as i64 {
+        return Err(StrawberryError::semantic_error(&format!(
+            "Index {index} of '{function_name}' out of bounds for string of length {length}"
+        )));
+    }
+
+    Ok(position as usize)
+}
+
+pub fn len(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 1, "len")?;
+    let string = expect_string(&args, 0, "len")?;
+    Ok(StrawberryValue::Number(string.chars().count() as f64))
+}
+
+pub fn upper(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 1, "upper")?;
+    Ok(StrawberryValue::String(expect_string(&args, 0, "upper")?.to_uppercase()))
+}
+
+pub fn lower(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 1, "lower")?;
+    Ok(StrawberryValue::String(expect_string(&args, 0, "lower")?.to_lowercase()))
+}
+
+pub fn trim(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 1, "trim")?;
+    Ok(StrawberryValue::String(expect_string(&args, 0, "trim")?.trim().to_string()))
+}
+
+pub fn split(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 2, "split")?;
+    let string = expect_string(&args, 0, "split")?;
+    let separator = expect_string(&args, 1, "split")?;
+
+    let parts: Vec<StrawberryValue> = if separator.is_empty() {
+        string.chars().map(|c| StrawberryValue::String(c.to_string())).collect()
+    } else {
+        string.split(separator).map(|part| StrawberryValue::String(part.to_string())).collect()
+    };
+
+    Ok(StrawberryValue::List(Rc::new(RefCell::new(parts))))
+}
+
+pub fn join(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 2, "join")?;
+    let separator = expect_string(&args, 1, "join")?;
+
+    if let StrawberryValue::List(list) = &args[0] {
+        let parts: Vec<String> = list.borrow().iter().map(StrawberryValue::to_string).collect();
+        Ok(StrawberryValue::String(parts.join(separator)))
+    } else {
+        Err(StrawberryError::semantic_error("First argument of 'join' must be a list"))
+    }
+}
+
+pub fn replace(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 3, "replace")?;
+    let string = expect_string(&args, 0, "replace")?;
+    let from = expect_string(&args, 1, "replace")?;
+    let to = expect_string(&args, 2, "replace")?;
+
+    if from.is_empty() {
+        return Err(StrawberryError::semantic_error("Second argument of 'replace' must not be empty"));
+    }
+
+    Ok(StrawberryValue::String(string.replace(from, to)))
+}
+
+pub fn contains(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 2, "contains")?;
+    let string = expect_string(&args, 0, "contains")?;
+    let part = expect_string(&args, 1, "contains")?;
+    Ok(StrawberryValue::Boolean(string.contains(part)))
+}
+
+pub fn starts_with(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 2, "starts_with")?;
+    let string = expect_string(&args, 0, "starts_with")?;
+    let prefix = expect_string(&args, 1, "starts_with")?;
+    Ok(StrawberryValue::Boolean(string.starts_with(prefix)))
+}
+
+pub fn ends_with(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 2, "ends_with")?;
+    let string = expect_string(&args, 0, "ends_with")?;
+    let suffix = expect_string(&args, 1, "ends_with")?;
+    Ok(StrawberryValue::Boolean(string.ends_with(suffix)))
+}
+
+pub fn find(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 2, "find")?;
+    let string = expect_string(&args, 0, "find")?;
+    let part = expect_string(&args, 1, "find")?;
+
+    match string.find(part) {
+        Some(byte_index) => Ok(StrawberryValue::Number(string[..byte_index].chars().count() as f64)),
+        None => Ok(StrawberryValue::Nil)
+    }
+}
+
+pub fn repeat(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 2, "repeat")?;
+    let string = expect_string(&args, 0, "repeat")?;
+    let count = expect_integer(&args, 1, "repeat")?;
+
+    if count < 0 {
+        return Err(StrawberryError::semantic_error("Second argument of 'repeat' must not be negative"));
+    }
+
+    expect_length(string.len().checked_mul(count as usize), "repeat")?;
+    Ok(StrawberryValue::String(string.repeat(count as usize)))
+}
+
+fn expect_length(length: Option<usize>, function_name: &str) -> Result<(), StrawberryError> {
+    match length {
+        Some(length) if length <= MAX_STRING_LENGTH => Ok(()),
+        _ => Err(StrawberryError::semantic_error(&format!(
+            "Result of '{function_name}' would be longer than {MAX_STRING_LENGTH} bytes"
+        )))
+    }
+}
+
+fn padding(args: &[StrawberryValue], function_name: &str) -> Result<String, StrawberryError> {
+    expect_argument_range(args, 2, 3, function_name)?;
+    let string = expect_string(args, 0, function_name)?;
+    let width = expect_integer(args, 1, function_name)?;
+
+    let fill = if args.len() == 3 {
+        let fill = expect_string(args, 2, function_name)?;
+        let mut characters = fill.chars();
+        match (characters.next(), characters.next()) {
+            (Some(fill), None) => fill,
+            _ => {
+                return Err(StrawberryError::semantic_error(&format!(
+                    "Third argument of '{function_name}' must be a single character"
+                )))
+            }
+        }
+    } else {
+        ' '
+    };
+
+    let missing = (width.max(0) as usize).saturating_sub(string.chars().count());
+    let length = missing.checked_mul(fill.len_utf8()).and_then(|length| length.checked_add(string.len()));
+    expect_length(length, function_name)?;
+    Ok(fill.to_string().repeat(missing))
+}
+
+pub fn pad_left(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    let padding = padding(&args, "pad_left")?;
+    Ok(StrawberryValue::String(padding + expect_string(&args, 0, "pad_left")?))
+}
+
+pub fn pad_right(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    let padding = padding(&args, "pad_right")?;
+    Ok(StrawberryValue::String(expect_string(&args, 0, "pad_right")?.to_string() + &padding))
+}
+
+pub fn substr(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_argument_range(&args, 2, 3, "substr")?;
+    let characters: Vec<char> = expect_string(&args, 0, "substr")?.chars().collect();
+
+    let start = character_position(expect_integer(&args, 1, "substr")?, characters.len(), "substr")?;
+    let end = if args.len() == 3 {
+        character_position(expect_integer(&args, 2, "substr")?, characters.len(), "substr")?
+    } else {
+        characters.len()
+    };
+
+    if start > end {
+        return Ok(StrawberryValue::String(String::new()));
+    }
+
+    Ok(StrawberryValue::String(characters[start..end].iter().collect()))
+}
\ No newline at end of file
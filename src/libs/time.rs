@@ -0,0 +1,29 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::{error::StrawberryError, parser::{StrawberryParser, StrawberryValue}};
+
+use super::expect_arguments;
+
+pub fn now(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 0, "now")?;
+
+    let elapsed = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    Ok(StrawberryValue::Number(elapsed.as_secs_f64()))
+}
+
+pub fn sleep(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 1, "sleep")?;
+
+    let duration = match &args[0] {
+        StrawberryValue::Number(seconds) => Duration::try_from_secs_f64(*seconds).ok(),
+        _ => None
+    };
+
+    match duration {
+        Some(duration) => {
+            std::thread::sleep(duration);
+            Ok(StrawberryValue::Empty)
+        },
+        None => Err(StrawberryError::semantic_error("Argument of 'sleep' must be a non-negative, representable number of seconds"))
+    }
+}
\ No newline at end of file
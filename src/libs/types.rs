@@ -0,0 +1,47 @@
+use crate::{error::StrawberryError, parser::{StrawberryParser, StrawberryValue}};
+
+use super::expect_arguments;
+
+pub fn type_of(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 1, "type_of")?;
+    Ok(StrawberryValue::String(args[0].type_name().to_string()))
+}
+
+pub fn to_string(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 1, "to_string")?;
+    Ok(StrawberryValue::String(args[0].to_string()))
+}
+
+pub fn to_number(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 1, "to_number")?;
+
+    match &args[0] {
+        StrawberryValue::Number(number) => Ok(StrawberryValue::Number(*number)),
+        StrawberryValue::Boolean(boolean) => Ok(StrawberryValue::Number(if *boolean { 1.0 } else { 0.0 })),
+        StrawberryValue::String(string) => match string.trim().parse::<f64>() {
+            Ok(number) if number.is_finite() => Ok(StrawberryValue::Number(number)),
+            _ => Ok(StrawberryValue::Nil)
+        },
+        StrawberryValue::Nil => Ok(StrawberryValue::Nil),
+        value => Err(StrawberryError::semantic_error(&format!(
+            "Cannot convert a {} to a number",
+            value.type_name()
+        )))
+    }
+}
+
+pub fn to_bool(args: Vec<StrawberryValue>, _: &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError> {
+    expect_arguments(&args, 1, "to_bool")?;
+
+    let boolean = match &args[0] {
+        StrawberryValue::Boolean(boolean) => *boolean,
+        StrawberryValue::Number(number) => *number != 0.0 && !number.is_nan(),
+        StrawberryValue::String(string) => !string.is_empty(),
+        StrawberryValue::List(list) => !list.borrow().is_empty(),
+        StrawberryValue::Map(map) => !map.borrow().is_empty(),
+        StrawberryValue::Nil | StrawberryValue::Empty => false,
+        _ => true
+    };
+
+    Ok(StrawberryValue::Boolean(boolean))
+}
\ No newline at end of file
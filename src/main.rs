@@ -1,20 +1,77 @@
 mod error;
 mod lexer;
+mod module;
 mod parser;
 
 mod libs;
 
-use std::{collections::HashMap, env::current_dir, path::PathBuf};
+use rand::{rngs::StdRng, SeedableRng};
+use std::{cell::RefCell, collections::HashMap, env::current_dir, path::PathBuf, rc::Rc, thread};
 
-use error::{StrawberryError, StrawberryErrorKind};
+use error::{format_call_chain, StrawberryError, StrawberryErrorKind};
 use lexer::StrawberryLexer;
+use module::{ModuleRegistry, ModuleScope};
 use libs::load_standard;
-use parser::{StrawberryParser, StrawberryValue};
+use parser::{StrawberryParser, StrawberryValue, DEFAULT_MAX_CALL_DEPTH};
 
-fn load_file(file_name: &str) -> Result<StrawberryValue, StrawberryError> {
+// Rough upper bound of native stack used by one Strawberry call, nested expressions included
+const STACK_BYTES_PER_CALL: usize = 64 * 1024;
+const MIN_STACK_SIZE: usize = 8 * 1024 * 1024;
+const MAX_STACK_SIZE: usize = 1024 * 1024 * 1024;
+const MAX_CALL_DEPTH_LIMIT: usize = MAX_STACK_SIZE / STACK_BYTES_PER_CALL;
+
+struct CliOptions {
+    file_name: String,
+    max_call_depth: usize,
+    strict: bool,
+    seed: Option<i64>
+}
+
+fn parse_arguments() -> Result<CliOptions, String> {
+    let mut options = CliOptions {
+        file_name: String::new(),
+        max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+        strict: false,
+        seed: None
+    };
+
+    let mut arguments = std::env::args().skip(1);
+    while let Some(argument) = arguments.next() {
+        match argument.as_str() {
+            "--max-call-depth" => {
+                let value = arguments.next().unwrap_or_default();
+                options.max_call_depth = match value.parse() {
+                    Ok(depth) if depth > MAX_CALL_DEPTH_LIMIT => {
+                        return Err(format!("Invalid value for --max-call-depth: {value:?} exceeds the maximum of {MAX_CALL_DEPTH_LIMIT}"))
+                    },
+                    Ok(depth) if depth > 0 => depth,
+                    _ => return Err(format!("Invalid value for --max-call-depth: {value:?}"))
+                };
+            },
+            "--strict" => options.strict = true,
+            "--seed" => {
+                let value = arguments.next().unwrap_or_default();
+                options.seed = match value.parse() {
+                    Ok(seed) => Some(seed),
+                    _ => return Err(format!("Invalid value for --seed: {value:?}"))
+                };
+            },
+            _ if argument.starts_with("--") => return Err(format!("Unknown option {argument}")),
+            _ => options.file_name = argument
+        }
+    }
+
+    if options.file_name.is_empty() {
+        return Err("Missing file name".into());
+    }
+
+    Ok(options)
+}
+
+fn load_file(options: &CliOptions) -> Result<StrawberryValue, StrawberryError> {
     let mut file_path = PathBuf::new();
     file_path.push(current_dir().unwrap_or_default());
-    file_path.push(file_name);
+    file_path.push(&options.file_name);
 
     if let Some(extesion) = file_path.extension() {
         if !extesion.eq("sb") {
@@ -26,7 +83,7 @@ fn load_file(file_name: &str) -> Result<StrawberryValue, StrawberryError> {
         return Ok(StrawberryValue::Empty);
     }
 
-    let file = std::fs::read_to_string(file_path);
+    let file = std::fs::read_to_string(&file_path);
     match file {
         Ok(source) => {
             let mut lexer = StrawberryLexer::from_string(&source);
@@ -36,6 +93,13 @@ fn load_file(file_name: &str) -> Result<StrawberryValue, StrawberryError> {
                 HashMap::new()
             );
 
+            parser.max_call_depth = options.max_call_depth;
+            parser.strict = options.strict;
+            parser.modules = Rc::new(RefCell::new(ModuleRegistry::new(&file_path)));
+            parser.module_scope = Rc::new(ModuleScope::new(file_path));
+            if let Some(seed) = options.seed {
+                parser.rng = Rc::new(RefCell::new(StdRng::seed_from_u64(seed as u64)));
+            }
             load_standard(&mut parser);
 
             Ok(parser.run_token_stream()?)
@@ -48,21 +112,43 @@ fn load_file(file_name: &str) -> Result<StrawberryValue, StrawberryError> {
 }
 
 fn main() {
-    let mut arguments = std::env::args();
-    let file_name = arguments.nth(1).unwrap_or_default();
+    let options = match parse_arguments() {
+        Ok(options) => options,
+        Err(message) => {
+            println!("{message}");
+            return;
+        }
+    };
 
-    if file_name.is_empty() {
-        println!("Missing file name");
-        return;
-    }
+    // Strawberry calls recurse on the native stack, so it has to fit the configured call depth
+    let stack_size = options.max_call_depth
+        .saturating_mul(STACK_BYTES_PER_CALL)
+        .clamp(MIN_STACK_SIZE, MAX_STACK_SIZE);
+
+    let interpreter = thread::Builder::new()
+        .stack_size(stack_size)
+        .spawn(move || {
+            if let Err(error) = load_file(&options) {
+                match error.kind {
+                    StrawberryErrorKind::SyntaxError(message) => println!("Syntax error: {message}"),
+                    StrawberryErrorKind::SemanticError(message) => println!("Semantic error: {message}"),
+                    StrawberryErrorKind::StackOverflow(max_call_depth, call_chain) => {
+                        println!("Stack overflow: maximum call depth of {max_call_depth} exceeded");
+                        println!("Call chain: {}", format_call_chain(&call_chain));
+                    },
+                    StrawberryErrorKind::Return(_) | StrawberryErrorKind::TailCall(_) => {
+                        println!("Semantic error: 'return' can only be used inside a function")
+                    }
+                }
+            }
+        });
 
-    match load_file(&file_name) {
-        Err(error) => {
-            match error.kind {
-                StrawberryErrorKind::SyntaxError(message) => println!("Syntax error: {message}"),
-                StrawberryErrorKind::SemanticError(message) => println!("Semantic error: {message}")
+    match interpreter {
+        Ok(handle) => {
+            if handle.join().is_err() {
+                std::process::exit(101);
             }
         },
-        _ => ()
+        Err(error) => println!("Could not start the interpreter: {error}")
     }
 }
\ No newline at end of file
@@ -0,0 +1,114 @@
+use std::{cell::RefCell, collections::{BTreeMap, HashMap, HashSet}, fmt, fs, path::{Path, PathBuf}, rc::Rc};
+
+use crate::{error::StrawberryError, lexer::{ModulePath, StrawberryLexer}, libs::{load_standard, standard_module}, parser::{StrawberryParser, StrawberryValue}};
+
+#[derive(Debug)]
+pub struct Module {
+    pub name: String,
+    pub exports: BTreeMap<String, StrawberryValue>,
+}
+
+#[derive(Default)]
+pub struct ModuleScope {
+    pub file_path: PathBuf,
+    pub variables: RefCell<HashMap<String, StrawberryValue>>,
+    pub constants: RefCell<HashSet<String>>,
+}
+
+impl ModuleScope {
+    pub fn new(file_path: PathBuf) -> Self {
+        Self {
+            file_path,
+            ..Self::default()
+        }
+    }
+}
+
+// Functions hold the scope of their file, which holds the functions in turn
+impl fmt::Debug for ModuleScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ModuleScope({})", self.file_path.display())
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ModuleRegistry {
+    cache: HashMap<PathBuf, Rc<Module>>,
+    standard: HashMap<String, Rc<Module>>,
+    loading: Vec<PathBuf>,
+}
+
+impl ModuleRegistry {
+    pub fn new(entry_path: &Path) -> Self {
+        Self {
+            loading: vec![entry_path.canonicalize().unwrap_or(entry_path.to_path_buf())],
+            ..Self::default()
+        }
+    }
+}
+
+fn display_name(path: &Path) -> String {
+    path.file_name().unwrap_or_default().to_string_lossy().into_owned()
+}
+
+pub fn import_module(parser: &StrawberryParser, path: &ModulePath) -> Result<Rc<Module>, StrawberryError> {
+    match path {
+        ModulePath::File(path) => import_file(parser, path),
+        ModulePath::Standard(name) => import_standard(parser, name),
+    }
+}
+
+fn import_standard(parser: &StrawberryParser, name: &str) -> Result<Rc<Module>, StrawberryError> {
+    if let Some(module) = parser.modules.borrow().standard.get(name) {
+        return Ok(module.clone());
+    }
+
+    let module = Rc::new(standard_module(name).ok_or_else(|| {
+        StrawberryError::semantic_error(&format!("Unknown standard module '{name}'; file modules are imported with a path in quotes"))
+    })?);
+    parser.modules.borrow_mut().standard.insert(name.to_string(), module.clone());
+    Ok(module)
+}
+
+fn import_file(parser: &StrawberryParser, path: &str) -> Result<Rc<Module>, StrawberryError> {
+    let importer_directory = parser.module_scope.file_path.parent().unwrap_or(Path::new(""));
+    let file_path = importer_directory.join(path).canonicalize().map_err(|error| {
+        StrawberryError::semantic_error(&format!("Could not open module '{path}': {error}"))
+    })?;
+
+    if let Some(module) = parser.modules.borrow().cache.get(&file_path) {
+        return Ok(module.clone());
+    }
+
+    if let Some(position) = parser.modules.borrow().loading.iter().position(|loading| *loading == file_path) {
+        let mut cycle: Vec<String> = parser.modules.borrow().loading[position..].iter().map(|loading| display_name(loading)).collect();
+        cycle.push(display_name(&file_path));
+        return Err(StrawberryError::semantic_error(&format!("Import cycle detected: {}", cycle.join(" -> "))));
+    }
+
+    let source = fs::read_to_string(&file_path).map_err(|error| {
+        StrawberryError::semantic_error(&format!("Could not open module '{path}': {error}"))
+    })?;
+
+    parser.modules.borrow_mut().loading.push(file_path.clone());
+    let result = run_module(parser, &file_path, &source);
+    parser.modules.borrow_mut().loading.pop();
+
+    let module = Rc::new(result?);
+    parser.modules.borrow_mut().cache.insert(file_path, module.clone());
+    Ok(module)
+}
+
+fn run_module(parser: &StrawberryParser, file_path: &Path, source: &str) -> Result<Module, StrawberryError> {
+    let mut lexer = StrawberryLexer::from_string(source);
+    let token_stream = lexer.run_stream()?;
+
+    let mut module_parser = parser.module(token_stream, file_path.to_path_buf());
+    load_standard(&mut module_parser);
+    module_parser.run_token_stream()?;
+
+    Ok(Module {
+        name: display_name(file_path),
+        exports: module_parser.exported_values(),
+    })
+}
\ No newline at end of file
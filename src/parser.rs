@@ -1,27 +1,337 @@
-use std::collections::HashMap;
-use crate::{error::StrawberryError, lexer::{ComparisonKind, ExpressionKind, Token, TokenKind}};
+use rand::{rngs::StdRng, SeedableRng};
+use std::{cell::RefCell, collections::{BTreeMap, HashMap, HashSet}, fmt, path::PathBuf, rc::Rc};
+use crate::{error::{StrawberryError, StrawberryErrorKind}, module::{import_module, Module, ModuleRegistry, ModuleScope}, lexer::{ComparisonKind, ExpressionKind, Parameter, Pattern, Token, TokenKind}};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone)]
 pub enum StrawberryValue {
     String(String),
     Number(f64),
     Boolean(bool),
     NativeFunction(String, fn(Vec<StrawberryValue>, &mut StrawberryParser) -> Result<StrawberryValue, StrawberryError>),
-    Function(String, Vec<String>, Vec<Box<Token>>),
-    Block(Vec<Box<Token>>),
+    Function(String, Vec<Parameter>, Vec<Token>, Rc<ModuleScope>),
+    Block(Vec<Token>),
+    List(Rc<RefCell<Vec<StrawberryValue>>>),
+    Map(Rc<RefCell<BTreeMap<String, StrawberryValue>>>),
+    Struct(Rc<StructDefinition>),
+    Record(Rc<RefCell<Record>>),
+    Enum(Rc<EnumDefinition>),
+    VariantConstructor(Rc<EnumDefinition>, usize),
+    Variant(Rc<EnumDefinition>, usize, Vec<StrawberryValue>),
+    Module(Rc<Module>),
+    Dynamic(String, fn(&StrawberryParser) -> Result<StrawberryValue, StrawberryError>),
+    Nil,
     Empty,
 }
 
+impl StrawberryValue {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            StrawberryValue::String(_) => "string",
+            StrawberryValue::Number(_) => "number",
+            StrawberryValue::Boolean(_) => "boolean",
+            StrawberryValue::NativeFunction(_, _) => "native",
+            StrawberryValue::Function(_, _, _, _)
+            | StrawberryValue::VariantConstructor(_, _) => "function",
+            StrawberryValue::Block(_) => "block",
+            StrawberryValue::List(_) => "list",
+            StrawberryValue::Map(_) => "map",
+            StrawberryValue::Struct(definition) if definition.is_class => "class",
+            StrawberryValue::Struct(_) => "struct",
+            StrawberryValue::Record(_) => "object",
+            StrawberryValue::Enum(_) => "enum",
+            StrawberryValue::Variant(_, _, _) => "variant",
+            StrawberryValue::Module(_) => "module",
+            StrawberryValue::Dynamic(_, _) => "dynamic",
+            StrawberryValue::Nil => "nil",
+            StrawberryValue::Empty => "empty",
+        }
+    }
+}
+
+impl fmt::Display for StrawberryValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StrawberryValue::String(string) => write!(f, "{string}"),
+            StrawberryValue::Number(number) => write!(f, "{number}"),
+            StrawberryValue::NativeFunction(name, _) => write!(f, "(Native Function: {name})"),
+            StrawberryValue::Function(name, _, _, _) => write!(f, "(Function: {name})"),
+            StrawberryValue::Boolean(boolean) => write!(f, "{boolean}"),
+            StrawberryValue::Block(_) => write!(f, "(Code block)"),
+            StrawberryValue::List(_) | StrawberryValue::Map(_) => write!(f, "{}", format_value(self, &mut Vec::new())),
+            StrawberryValue::Struct(definition) if definition.is_class => write!(f, "(Class: {})", definition.name),
+            StrawberryValue::Struct(definition) => write!(f, "(Struct: {})", definition.name),
+            StrawberryValue::Record(_) => write!(f, "{}", format_value(self, &mut Vec::new())),
+            StrawberryValue::Enum(definition) => write!(f, "(Enum: {})", definition.name),
+            StrawberryValue::VariantConstructor(definition, index) => {
+                write!(f, "(Variant: {}.{})", definition.name, definition.variants[*index].0)
+            }
+            StrawberryValue::Variant(..) => write!(f, "{}", format_value(self, &mut Vec::new())),
+            StrawberryValue::Module(module) => write!(f, "(Module: {})", module.name),
+            StrawberryValue::Dynamic(name, _) => write!(f, "(Dynamic: {name})"),
+            StrawberryValue::Nil => write!(f, "nil"),
+            StrawberryValue::Empty => write!(f, "(Empty)"),
+        }
+    }
+}
+
+fn format_key(key: &str) -> String {
+    let is_identifier = key.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && key.chars().all(|c| c.is_alphanumeric() || c == '_');
+
+    if is_identifier {
+        key.to_string()
+    } else {
+        format!("'{key}'")
+    }
+}
+
+fn format_element(value: &StrawberryValue, visiting: &mut Vec<*const ()>) -> String {
+    match value {
+        StrawberryValue::String(string) => format!("'{string}'"),
+        _ => format_value(value, visiting),
+    }
+}
+
+fn format_value(value: &StrawberryValue, visiting: &mut Vec<*const ()>) -> String {
+    let pointer = match value {
+        StrawberryValue::List(list) => Rc::as_ptr(list) as *const (),
+        StrawberryValue::Map(map) => Rc::as_ptr(map) as *const (),
+        StrawberryValue::Record(record) => Rc::as_ptr(record) as *const (),
+        StrawberryValue::Variant(definition, index, values) => {
+            let variant_name = &definition.variants[*index].0;
+            if values.is_empty() {
+                return variant_name.to_string();
+            }
+            let values: Vec<String> = values.iter().map(|value| format_element(value, visiting)).collect();
+            return format!("{variant_name}({})", values.join(", "));
+        }
+        _ => return value.to_string(),
+    };
+
+    if visiting.contains(&pointer) {
+        return match value {
+            StrawberryValue::List(_) => "[...]".to_string(),
+            StrawberryValue::Record(record) => format!("{} {{...}}", record.borrow().definition.name),
+            _ => "{...}".to_string(),
+        };
+    }
+
+    visiting.push(pointer);
+    let formatted = match value {
+        StrawberryValue::List(list) => {
+            let elements: Vec<String> = list.borrow().iter().map(|element| format_element(element, visiting)).collect();
+            format!("[{}]", elements.join(", "))
+        }
+        StrawberryValue::Map(map) if map.borrow().is_empty() => "{}".to_string(),
+        StrawberryValue::Map(map) => {
+            let entries: Vec<String> = map.borrow().iter().map(|(key, value)| {
+                format!("{}: {}", format_key(key), format_element(value, visiting))
+            }).collect();
+            format!("{{ {} }}", entries.join(", "))
+        }
+        StrawberryValue::Record(record) => {
+            let record = record.borrow();
+            let fields: Vec<String> = record.definition.fields.iter().zip(&record.values).map(|(field, value)| {
+                format!("{field}: {}", format_element(value, visiting))
+            }).collect();
+            format!("{} {{ {} }}", record.definition.name, fields.join(", "))
+        }
+        _ => value.to_string(),
+    };
+    visiting.pop();
+    formatted
+}
+
+#[derive(Debug)]
+pub struct EnumDefinition {
+    pub name: String,
+    pub variants: Vec<(String, Vec<String>)>,
+}
+
+impl EnumDefinition {
+    pub fn variant(self: &Rc<Self>, name: &str) -> Result<StrawberryValue, StrawberryError> {
+        match self.variants.iter().position(|(variant_name, _)| variant_name == name) {
+            Some(index) if self.variants[index].1.is_empty() => Ok(StrawberryValue::Variant(self.clone(), index, Vec::new())),
+            Some(index) => Ok(StrawberryValue::VariantConstructor(self.clone(), index)),
+            None => Err(StrawberryError::semantic_error(&format!(
+                "{} has no variant '{name}'",
+                self.name
+            ))),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct StructDefinition {
+    pub name: String,
+    pub fields: Vec<String>,
+    pub methods: HashMap<String, StrawberryValue>,
+    pub is_class: bool,
+}
+
+#[derive(Debug)]
+pub struct Record {
+    pub definition: Rc<StructDefinition>,
+    pub values: Vec<StrawberryValue>,
+}
+
+impl Record {
+    pub fn field(&self, name: &str) -> Result<StrawberryValue, StrawberryError> {
+        match self.definition.fields.iter().position(|field| field == name) {
+            Some(position) => Ok(self.values[position].clone()),
+            None => Err(StrawberryError::semantic_error(&format!(
+                "{} has no field '{name}'",
+                self.definition.name
+            ))),
+        }
+    }
+
+    pub fn set_field(&mut self, name: &str, value: StrawberryValue) -> Result<(), StrawberryError> {
+        match self.definition.fields.iter().position(|field| field == name) {
+            Some(position) => {
+                self.values[position] = value;
+                Ok(())
+            }
+            None => Err(StrawberryError::semantic_error(&format!(
+                "{} has no field '{name}'",
+                self.definition.name
+            ))),
+        }
+    }
+}
+
+pub const DEFAULT_MAX_CALL_DEPTH: usize = 1000;
+
+type NamedArguments = Vec<(String, StrawberryValue)>;
+
+type ResolvedCall = (String, StrawberryValue, Vec<StrawberryValue>, NamedArguments);
+
+#[derive(Debug)]
+pub struct TailCall {
+    function_name: String,
+    params: Vec<Parameter>,
+    body: Vec<Token>,
+    args_values: Vec<StrawberryValue>,
+    named_values: NamedArguments,
+    module_scope: Rc<ModuleScope>,
+    scope: HashMap<String, StrawberryValue>,
+    constants: HashSet<String>,
+}
+
 pub struct StrawberryParser {
     tokens: Vec<Token>,
     pub variables: HashMap<String, StrawberryValue>,
+    constants: HashSet<String>,
+    pub max_call_depth: usize,
+    pub strict: bool,
+    call_stack: Rc<RefCell<Vec<String>>>,
+    in_function: bool,
+    tail_position: bool,
+    native_in_tail_position: bool,
+    warned_matches: Rc<RefCell<HashSet<usize>>>,
+    pub module_scope: Rc<ModuleScope>,
+    pub modules: Rc<RefCell<ModuleRegistry>>,
+    pub rng: Rc<RefCell<StdRng>>,
+    is_module_root: bool,
+    exports: Vec<String>,
 }
 
 impl StrawberryParser {
     pub fn new(tokens: Vec<Token>, variables: HashMap<String, StrawberryValue>) -> Self {
         Self {
             tokens,
-            variables
+            variables,
+            constants: HashSet::new(),
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            strict: false,
+            call_stack: Rc::new(RefCell::new(Vec::new())),
+            in_function: false,
+            tail_position: false,
+            native_in_tail_position: false,
+            warned_matches: Rc::new(RefCell::new(HashSet::new())),
+            module_scope: Rc::new(ModuleScope::default()),
+            modules: Rc::new(RefCell::new(ModuleRegistry::default())),
+            rng: Rc::new(RefCell::new(StdRng::from_entropy())),
+            is_module_root: true,
+            exports: Vec::new(),
+        }
+    }
+
+    pub fn child(&self, tokens: Vec<Token>, variables: HashMap<String, StrawberryValue>) -> Self {
+        Self {
+            constants: self.constants.clone(),
+            max_call_depth: self.max_call_depth,
+            strict: self.strict,
+            call_stack: self.call_stack.clone(),
+            in_function: self.in_function,
+            warned_matches: self.warned_matches.clone(),
+            module_scope: self.module_scope.clone(),
+            modules: self.modules.clone(),
+            rng: self.rng.clone(),
+            is_module_root: false,
+            ..Self::new(tokens, variables)
+        }
+    }
+
+    pub fn module(&self, tokens: Vec<Token>, file_path: PathBuf) -> Self {
+        Self {
+            max_call_depth: self.max_call_depth,
+            strict: self.strict,
+            call_stack: self.call_stack.clone(),
+            module_scope: Rc::new(ModuleScope::new(file_path)),
+            modules: self.modules.clone(),
+            rng: self.rng.clone(),
+            ..Self::new(tokens, HashMap::new())
+        }
+    }
+
+    pub fn exported_values(&self) -> BTreeMap<String, StrawberryValue> {
+        self.exports
+            .iter()
+            .filter_map(|name| self.variables.get(name).map(|value| (name.clone(), value.clone())))
+            .collect()
+    }
+
+    pub fn define_constant(&mut self, name: &str, value: StrawberryValue) {
+        self.variables.insert(name.to_string(), value);
+        self.constants.insert(name.to_string());
+        self.publish(name);
+    }
+
+    // The getter runs every time the name is read, so the value can change between reads
+    pub fn define_dynamic(&mut self, name: &str, getter: fn(&StrawberryParser) -> Result<StrawberryValue, StrawberryError>) {
+        self.define_constant(name, StrawberryValue::Dynamic(name.to_string(), getter));
+    }
+
+    fn declare(&mut self, name: &str, value: StrawberryValue) -> Result<(), StrawberryError> {
+        if self.constants.contains(name) {
+            return Err(StrawberryError::semantic_error(&format!("Cannot reassign constant '{name}'")));
+        }
+
+        self.variables.insert(name.to_string(), value);
+        self.publish(name);
+        Ok(())
+    }
+
+    // Top-level names of a file are what its functions see when called from another file
+    fn publish(&self, name: &str) {
+        if !self.is_module_root {
+            return;
+        }
+
+        if let Some(value) = self.variables.get(name) {
+            self.module_scope.variables.borrow_mut().insert(name.to_string(), value.clone());
+        }
+        if self.constants.contains(name) {
+            self.module_scope.constants.borrow_mut().insert(name.to_string());
+        }
+    }
+
+    fn function_scope(&self, module_scope: &Rc<ModuleScope>) -> (HashMap<String, StrawberryValue>, HashSet<String>) {
+        if Rc::ptr_eq(module_scope, &self.module_scope) {
+            (self.variables.clone(), self.constants.clone())
+        } else {
+            (module_scope.variables.borrow().clone(), module_scope.constants.borrow().clone())
         }
     }
 
@@ -52,9 +362,17 @@ impl StrawberryParser {
             (StrawberryValue::String(lhs), StrawberryValue::String(rhs)) => {
                 self.evaluate_string_expression(operator, lhs, rhs)
             }
-            _ => Err(StrawberryError::semantic_error(
-                "Cannot evaluate expression with mixed types",
-            )),
+            (StrawberryValue::String(lhs), rhs) if !self.strict => {
+                self.evaluate_string_expression(operator, lhs, rhs.to_string())
+            }
+            (lhs, StrawberryValue::String(rhs)) if !self.strict => {
+                self.evaluate_string_expression(operator, lhs.to_string(), rhs)
+            }
+            (lhs, rhs) => Err(StrawberryError::semantic_error(&format!(
+                "Cannot evaluate expression with mixed types {} and {}",
+                lhs.type_name(),
+                rhs.type_name()
+            ))),
         }
     }
 
@@ -94,14 +412,17 @@ impl StrawberryParser {
     }
 
     fn visit_let(&mut self, token: &Token) -> Result<StrawberryValue, StrawberryError> {
-        if let TokenKind::Let(name, value) = &token.kind {
+        if let TokenKind::Let(pattern, value) = &token.kind {
             let evaluated_value = if let Some(value_token) = value {
                 self.parse_token(value_token)?
             } else {
-                StrawberryValue::Empty
+                StrawberryValue::Nil
             };
 
-            self.variables.insert(name.clone(), evaluated_value.clone());
+            let bindings = self.destructure(pattern, &evaluated_value, "let statement")?;
+            for (name, value) in bindings {
+                self.declare(&name, value)?;
+            }
 
             Ok(evaluated_value)
         } else {
@@ -109,9 +430,47 @@ impl StrawberryParser {
         }
     }
 
+    fn visit_const(&mut self, token: &Token) -> Result<StrawberryValue, StrawberryError> {
+        if let TokenKind::Const(pattern, value_token) = &token.kind {
+            let evaluated_value = self.parse_token(value_token)?;
+
+            let bindings = self.destructure(pattern, &evaluated_value, "const statement")?;
+            for (name, value) in bindings {
+                self.declare(&name, value)?;
+                self.constants.insert(name.clone());
+                self.publish(&name);
+            }
+
+            Ok(evaluated_value)
+        } else {
+            Err(StrawberryError::semantic_error("Expected a Const token"))
+        }
+    }
+
+    fn destructure(
+        &mut self,
+        pattern: &Pattern,
+        value: &StrawberryValue,
+        context: &str,
+    ) -> Result<HashMap<String, StrawberryValue>, StrawberryError> {
+        let mut bindings = HashMap::new();
+
+        if let Pattern::Binding(name) = pattern {
+            bindings.insert(name.clone(), value.clone());
+        } else if !self.match_pattern(pattern, value, &mut bindings)? {
+            return Err(StrawberryError::semantic_error(&format!(
+                "The value {value} does not match the pattern of the {context}"
+            )));
+        }
+
+        Ok(bindings)
+    }
+
     fn visit_identifier(&self, token: &Token) -> Result<StrawberryValue, StrawberryError> {
         if let TokenKind::Identifier(name) = &token.kind {
-            if let Some(value) = self.variables.get(name) {
+            if let Some(StrawberryValue::Dynamic(_, getter)) = self.variables.get(name) {
+                getter(self)
+            } else if let Some(value) = self.variables.get(name) {
                 Ok(value.clone())
             } else {
                 Err(StrawberryError::semantic_error(&format!("Undefined variable: {}", name)))
@@ -121,57 +480,388 @@ impl StrawberryParser {
         }
     }
 
-    fn visit_call(&mut self, token: &Token) -> Result<StrawberryValue, StrawberryError> {
-        if let TokenKind::Call(function_name, args) = &token.kind {
-            let function = self.visit_identifier(&Token {
-                kind: TokenKind::Identifier(function_name.clone()),
-                ..token.clone()
-            })?;
-    
-            let args_values: Result<Vec<_>, _> = args.iter().map(|arg| self.parse_token(arg)).collect();
-            let args_values = args_values?;
-    
-            match function {
-                StrawberryValue::NativeFunction(_, func) => func(args_values, self),
-    
-                StrawberryValue::Function(_, params, body) => {
-                    if params.len() != args_values.len() {
-                        return Err(StrawberryError::semantic_error(&format!(
-                            "Function {} expected {} arguments, but got {}",
-                            function_name,
-                            params.len(),
-                            args_values.len()
-                        )));
+    fn evaluate_arguments(&mut self, args: &[Token]) -> Result<(Vec<StrawberryValue>, NamedArguments), StrawberryError> {
+        let mut args_values = Vec::new();
+        let mut named_values: NamedArguments = Vec::new();
+
+        for arg in args {
+            if let TokenKind::NamedArgument(name, value) = &arg.kind {
+                if named_values.iter().any(|(named, _)| named == name) {
+                    return Err(StrawberryError::semantic_error(&format!("Argument '{name}' was given more than once")));
+                }
+                let value = self.parse_token(value)?;
+                named_values.push((name.clone(), value));
+            } else if !named_values.is_empty() {
+                return Err(StrawberryError::semantic_error("Positional arguments cannot follow named arguments"));
+            } else {
+                args_values.push(self.parse_token(arg)?);
+            }
+        }
+
+        Ok((args_values, named_values))
+    }
+
+    fn resolve_call(&mut self, token: &Token) -> Result<Option<ResolvedCall>, StrawberryError> {
+        match &token.kind {
+            TokenKind::Call(function_name, args) => {
+                let function = self.visit_identifier(&Token {
+                    kind: TokenKind::Identifier(function_name.clone()),
+                    ..token.clone()
+                })?;
+
+                let (args_values, named_values) = self.evaluate_arguments(args)?;
+
+                Ok(Some((function_name.clone(), function, args_values, named_values)))
+            }
+            TokenKind::MethodCall(receiver, method_name, args)
+            | TokenKind::OptionalMethodCall(receiver, method_name, args) => {
+                let optional = matches!(token.kind, TokenKind::OptionalMethodCall(_, _, _));
+                match self.visit_chain(receiver)? {
+                    None => Ok(None),
+                    Some(StrawberryValue::Nil) if optional => Ok(None),
+                    Some(receiver_value) => self.resolve_method(receiver_value, method_name, args).map(Some),
+                }
+            }
+            _ => Err(StrawberryError::semantic_error("Expected a Call token")),
+        }
+    }
+
+    fn resolve_method(
+        &mut self,
+        receiver_value: StrawberryValue,
+        method_name: &String,
+        args: &[Token],
+    ) -> Result<ResolvedCall, StrawberryError> {
+        let (mut args_values, named_values) = self.evaluate_arguments(args)?;
+
+        match &receiver_value {
+            StrawberryValue::Record(record) => {
+                let record_binding = record.borrow();
+                let definition = &record_binding.definition;
+                let function_name = format!("{}.{method_name}", definition.name);
+
+                if let Some(method) = definition.methods.get(method_name) {
+                    let method = method.clone();
+                    drop(record_binding);
+                    args_values.insert(0, receiver_value);
+                    Ok((function_name, method, args_values, named_values))
+                } else if definition.fields.contains(method_name) {
+                    Ok((function_name, record_binding.field(method_name)?, args_values, named_values))
+                } else {
+                    Err(StrawberryError::semantic_error(&format!(
+                        "{} has no method '{method_name}'",
+                        definition.name
+                    )))
+                }
+            }
+            StrawberryValue::Map(map) => {
+                Ok((method_name.clone(), map_entry(&map.borrow(), method_name)?, args_values, named_values))
+            }
+            StrawberryValue::Enum(definition) => Ok((
+                format!("{}.{method_name}", definition.name),
+                definition.variant(method_name)?,
+                args_values,
+                named_values,
+            )),
+            StrawberryValue::Module(module) => Ok((
+                method_name.clone(),
+                module_export(module, method_name)?,
+                args_values,
+                named_values,
+            )),
+            _ => Err(StrawberryError::semantic_error(&format!(
+                "Cannot call '{method_name}' on a value that is not a map or an object"
+            ))),
+        }
+    }
+
+    fn visit_postfix(&mut self, token: &Token) -> Result<StrawberryValue, StrawberryError> {
+        Ok(self.visit_chain(token)?.unwrap_or(StrawberryValue::Nil))
+    }
+
+    // Evaluates one link of a member, index or method chain; `None` means an earlier `?.` met nil
+    fn visit_chain(&mut self, token: &Token) -> Result<Option<StrawberryValue>, StrawberryError> {
+        match &token.kind {
+            TokenKind::Member(target, name) | TokenKind::OptionalMember(target, name) => {
+                let optional = matches!(token.kind, TokenKind::OptionalMember(_, _));
+                match self.visit_chain(target)? {
+                    None => Ok(None),
+                    Some(StrawberryValue::Nil) if optional => Ok(None),
+                    Some(target_value) => member_of(target_value, name).map(Some),
+                }
+            }
+            TokenKind::Index(target, index) => match self.visit_chain(target)? {
+                None => Ok(None),
+                Some(target_value) => self.index_value(target_value, index).map(Some),
+            },
+            TokenKind::Call(_, _) | TokenKind::MethodCall(_, _, _) | TokenKind::OptionalMethodCall(_, _, _) => {
+                match self.resolve_call(token)? {
+                    None => Ok(None),
+                    Some((function_name, function, args_values, named_values)) => {
+                        self.call_value(function_name, function, args_values, named_values).map(Some)
                     }
-    
-                    let mut scope = self.variables.clone();
-                    for (param, value) in params.iter().zip(args_values.into_iter()) {
-                        scope.insert(param.clone(), value);
+                }
+            }
+            _ => self.parse_token(token).map(Some),
+        }
+    }
+
+    fn call_value(
+        &mut self,
+        function_name: String,
+        function: StrawberryValue,
+        args_values: Vec<StrawberryValue>,
+        named_values: NamedArguments,
+    ) -> Result<StrawberryValue, StrawberryError> {
+        let accepts_named = match &function {
+            StrawberryValue::Function(_, _, _, _) => true,
+            StrawberryValue::Struct(definition) => definition.methods.contains_key("init"),
+            _ => false,
+        };
+        if !accepts_named && !named_values.is_empty() {
+            return Err(StrawberryError::semantic_error(&format!("{function_name} does not accept named arguments")));
+        }
+
+        match function {
+            StrawberryValue::NativeFunction(_, func) => func(args_values, self),
+
+            StrawberryValue::Function(_, params, body, module_scope) => {
+                if self.call_stack.borrow().len() >= self.max_call_depth {
+                    let mut call_chain = self.call_stack.borrow().clone();
+                    call_chain.push(function_name);
+                    return Err(StrawberryError::stack_overflow(self.max_call_depth, call_chain));
+                }
+
+                let (scope, constants) = self.function_scope(&module_scope);
+                self.call_function(TailCall {
+                    function_name,
+                    params,
+                    body,
+                    args_values,
+                    named_values,
+                    module_scope,
+                    scope,
+                    constants,
+                })
+            }
+
+            StrawberryValue::Struct(definition) => {
+                if let Some(constructor) = definition.methods.get("init") {
+                    let record = StrawberryValue::Record(Rc::new(RefCell::new(Record {
+                        values: vec![StrawberryValue::Nil; definition.fields.len()],
+                        definition: definition.clone(),
+                    })));
+
+                    let mut constructor_args = args_values;
+                    constructor_args.insert(0, record.clone());
+                    self.call_value(
+                        format!("{}.init", definition.name),
+                        constructor.clone(),
+                        constructor_args,
+                        named_values,
+                    )?;
+
+                    return Ok(record);
+                }
+
+                if definition.fields.len() != args_values.len() {
+                    return Err(StrawberryError::semantic_error(&format!(
+                        "Struct {} expected {} fields, but got {}",
+                        definition.name,
+                        definition.fields.len(),
+                        args_values.len()
+                    )));
+                }
+
+                Ok(StrawberryValue::Record(Rc::new(RefCell::new(Record {
+                    definition,
+                    values: args_values,
+                }))))
+            }
+
+            StrawberryValue::VariantConstructor(definition, index) => {
+                let (variant_name, fields) = &definition.variants[index];
+                if fields.len() != args_values.len() {
+                    return Err(StrawberryError::semantic_error(&format!(
+                        "Variant {variant_name} expected {} values, but got {}",
+                        fields.len(),
+                        args_values.len()
+                    )));
+                }
+
+                Ok(StrawberryValue::Variant(definition, index, args_values))
+            }
+
+            _ => Err(StrawberryError::semantic_error(&format!(
+                "{} is not callable",
+                function_name
+            ))),
+        }
+    }
+
+    fn call_function(&mut self, call: TailCall) -> Result<StrawberryValue, StrawberryError> {
+        self.call_stack.borrow_mut().push(call.function_name.clone());
+        let result = self.run_function(call);
+        self.call_stack.borrow_mut().pop();
+        result
+    }
+
+    // Calls in tail position unwind back here instead of nesting, so the frame is reused
+    fn run_function(&mut self, mut call: TailCall) -> Result<StrawberryValue, StrawberryError> {
+        loop {
+            self.bind_arguments(&mut call)?;
+
+            if let Some(frame) = self.call_stack.borrow_mut().last_mut() {
+                frame.clone_from(&call.function_name);
+            }
+
+            let mut function_parser = self.child(call.body, call.scope);
+            function_parser.constants = call.constants;
+            function_parser.module_scope = call.module_scope;
+            function_parser.in_function = true;
+            function_parser.tail_position = true;
+
+            match function_parser.run_token_stream() {
+                Err(StrawberryError { kind: StrawberryErrorKind::TailCall(next_call) }) => call = *next_call,
+                Err(StrawberryError { kind: StrawberryErrorKind::Return(value) }) => return Ok(*value),
+                result => return result,
+            }
+        }
+    }
+
+    fn bind_arguments(&mut self, call: &mut TailCall) -> Result<(), StrawberryError> {
+        let fixed_count = call.params.iter().filter(|param| !param.variadic).count();
+        let is_variadic = fixed_count < call.params.len();
+
+        if !is_variadic && call.args_values.len() > fixed_count {
+            return Err(StrawberryError::semantic_error(&format!(
+                "Function {} expected at most {} arguments, but got {}",
+                call.function_name,
+                fixed_count,
+                call.args_values.len()
+            )));
+        }
+
+        for (name, _) in &call.named_values {
+            let is_parameter = call.params.iter().any(|param| {
+                !param.variadic && matches!(&param.pattern, Pattern::Binding(param_name) if param_name == name)
+            });
+            if !is_parameter {
+                return Err(StrawberryError::semantic_error(&format!(
+                    "Function {} has no parameter named '{name}'",
+                    call.function_name
+                )));
+            }
+        }
+
+        let mut positional = std::mem::take(&mut call.args_values).into_iter();
+        let mut named_values = std::mem::take(&mut call.named_values);
+
+        for (position, param) in call.params.iter().enumerate() {
+            let param_name = match &param.pattern {
+                Pattern::Binding(name) => format!("'{name}'"),
+                _ => format!("{}", position + 1),
+            };
+
+            let value = if param.variadic {
+                StrawberryValue::List(Rc::new(RefCell::new(positional.by_ref().collect())))
+            } else {
+                let named_index = match &param.pattern {
+                    Pattern::Binding(name) => named_values.iter().position(|(named, _)| named == name),
+                    _ => None,
+                };
+
+                match (positional.next(), named_index) {
+                    (Some(_), Some(_)) => {
+                        return Err(StrawberryError::semantic_error(&format!(
+                            "Parameter {param_name} of {} was given more than once",
+                            call.function_name
+                        )))
                     }
-    
-                    let mut result = StrawberryValue::Empty;
-                    result = StrawberryParser::new(body.iter().map(|t| *t.clone()).collect(), scope).run_token_stream()?;
-                    Ok(result)
+                    (Some(value), None) => value,
+                    (None, Some(index)) => named_values.remove(index).1,
+                    (None, None) => match &param.default {
+                        Some(default) => {
+                            let mut default_parser = self.child(Vec::new(), call.scope.clone());
+                            default_parser.constants = call.constants.clone();
+                            default_parser.parse_token(default)?
+                        }
+                        None => {
+                            return Err(StrawberryError::semantic_error(&format!(
+                                "Function {} is missing the argument for parameter {param_name}",
+                                call.function_name
+                            )))
+                        }
+                    },
                 }
-    
-                _ => Err(StrawberryError::semantic_error(&format!(
-                    "{} is not callable",
-                    function_name
-                ))),
+            };
+
+            // Parameters open a new scope, so they may shadow constants
+            let context = format!("argument {} of {}", position + 1, call.function_name);
+            let bindings = self.destructure(&param.pattern, &value, &context)?;
+            for (name, value) in bindings {
+                call.constants.remove(&name);
+                call.scope.insert(name, value);
             }
-        } else {
-            Err(StrawberryError::semantic_error("Expected a Call token"))
         }
+
+        Ok(())
+    }
+
+    fn visit_tail_call(&mut self, token: &Token) -> Result<StrawberryValue, StrawberryError> {
+        let Some((function_name, function, args_values, named_values)) = self.resolve_call(token)? else {
+            return Ok(StrawberryValue::Nil);
+        };
+
+        match function {
+            StrawberryValue::NativeFunction(_, func) if named_values.is_empty() => {
+                self.native_in_tail_position = true;
+                let result = func(args_values, self);
+                self.native_in_tail_position = false;
+                result
+            }
+
+            StrawberryValue::Function(_, params, body, module_scope) => {
+                let (scope, constants) = self.function_scope(&module_scope);
+                Err(StrawberryError::tail_call(TailCall {
+                    function_name,
+                    params,
+                    body,
+                    args_values,
+                    named_values,
+                    module_scope,
+                    scope,
+                    constants,
+                }))
+            }
+
+            _ => self.call_value(function_name, function, args_values, named_values),
+        }
+    }
+
+    fn parse_tail_token(&mut self, token: &Token) -> Result<StrawberryValue, StrawberryError> {
+        match &token.kind {
+            TokenKind::Call(_, _)
+            | TokenKind::MethodCall(_, _, _) => self.visit_tail_call(token),
+            TokenKind::Match(_, _) => self.visit_match(token, true),
+            TokenKind::Conditional(_, _, _) => self.visit_conditional(token, true),
+            _ => self.parse_token(token),
+        }
+    }
+
+    pub fn execute_block(&self, tokens: Vec<Token>) -> Result<StrawberryValue, StrawberryError> {
+        let mut block_parser = self.child(tokens, self.variables.clone());
+        block_parser.tail_position = self.native_in_tail_position;
+        block_parser.run_token_stream()
     }
-    
 
     fn visit_function(&mut self, token: &Token) -> Result<StrawberryValue, StrawberryError> {
         if let TokenKind::Function(name, arguments, scope) = &token.kind {
             if let TokenKind::BracketScope(tokens) = &scope.kind {
-                self.variables.insert(
-                    name.clone(),
-                    StrawberryValue::Function(name.clone(), arguments.clone(), tokens.clone().iter().map(|t| Box::new(t.clone())).collect()),
-                );
+                self.declare(
+                    name,
+                    StrawberryValue::Function(name.clone(), arguments.clone(), tokens.clone(), self.module_scope.clone()),
+                )?;
             }
             Ok(StrawberryValue::Empty)
         } else {
@@ -179,64 +869,503 @@ impl StrawberryParser {
         }
     }
 
-    fn evaluate_comparison(
-        &self,
-        operator: ComparisonKind,
-        left: StrawberryValue,
-        right: StrawberryValue,
-    ) -> Result<StrawberryValue, StrawberryError> {
-        match (operator, left, right) {
-            (ComparisonKind::Equal, StrawberryValue::Number(lhs), StrawberryValue::Number(rhs)) => {
-                Ok(StrawberryValue::Boolean(lhs == rhs))
+    fn visit_import(&mut self, token: &Token) -> Result<StrawberryValue, StrawberryError> {
+        match &token.kind {
+            TokenKind::Import(path, alias) => {
+                let module = import_module(self, path)?;
+                self.declare(alias, StrawberryValue::Module(module))?;
+                Ok(StrawberryValue::Empty)
+            }
+            TokenKind::ImportNames(path, names) => {
+                let module = import_module(self, path)?;
+                for name in names {
+                    self.declare(name, module_export(&module, name)?)?;
+                }
+                Ok(StrawberryValue::Empty)
+            }
+            _ => Err(StrawberryError::semantic_error("Expected an Import token")),
+        }
+    }
+
+    fn visit_export(&mut self, token: &Token) -> Result<StrawberryValue, StrawberryError> {
+        if let TokenKind::Export(declaration) = &token.kind {
+            if !self.is_module_root {
+                return Err(StrawberryError::semantic_error("'export' can only be used at the top level of a file"));
             }
-            (ComparisonKind::NotEqual, StrawberryValue::Number(lhs), StrawberryValue::Number(rhs)) => {
-                Ok(StrawberryValue::Boolean(lhs != rhs))
+
+            let value = self.parse_token(declaration)?;
+
+            match &declaration.kind {
+                TokenKind::Function(name, _, _)
+                | TokenKind::Struct(name, _)
+                | TokenKind::Class(name, _, _)
+                | TokenKind::Enum(name, _) => self.exports.push(name.clone()),
+                TokenKind::Let(pattern, _)
+                | TokenKind::Const(pattern, _) => binding_names(pattern, &mut self.exports),
+                _ => {}
             }
-            (ComparisonKind::GreaterThan, StrawberryValue::Number(lhs), StrawberryValue::Number(rhs)) => {
-                Ok(StrawberryValue::Boolean(lhs > rhs))
+
+            Ok(value)
+        } else {
+            Err(StrawberryError::semantic_error("Expected an Export token"))
+        }
+    }
+
+    fn visit_struct(&mut self, token: &Token) -> Result<StrawberryValue, StrawberryError> {
+        if let TokenKind::Struct(name, fields) = &token.kind {
+            self.declare(
+                name,
+                StrawberryValue::Struct(Rc::new(StructDefinition {
+                    name: name.clone(),
+                    fields: fields.clone(),
+                    methods: HashMap::new(),
+                    is_class: false,
+                })),
+            )?;
+            Ok(StrawberryValue::Empty)
+        } else {
+            Err(StrawberryError::semantic_error("Expected a Struct token"))
+        }
+    }
+
+    fn visit_class(&mut self, token: &Token) -> Result<StrawberryValue, StrawberryError> {
+        if let TokenKind::Class(name, fields, methods) = &token.kind {
+            let mut method_values = HashMap::new();
+            for method in methods {
+                if let TokenKind::Function(method_name, arguments, body) = &method.kind {
+                    if let TokenKind::BracketScope(tokens) = &body.kind {
+                        method_values.insert(
+                            method_name.clone(),
+                            StrawberryValue::Function(
+                                method_name.clone(),
+                                arguments.clone(),
+                                tokens.clone(),
+                                self.module_scope.clone(),
+                            ),
+                        );
+                    }
+                }
+            }
+
+            self.declare(
+                name,
+                StrawberryValue::Struct(Rc::new(StructDefinition {
+                    name: name.clone(),
+                    fields: fields.clone(),
+                    methods: method_values,
+                    is_class: true,
+                })),
+            )?;
+            Ok(StrawberryValue::Empty)
+        } else {
+            Err(StrawberryError::semantic_error("Expected a Class token"))
+        }
+    }
+
+    fn visit_enum(&mut self, token: &Token) -> Result<StrawberryValue, StrawberryError> {
+        if let TokenKind::Enum(name, variants) = &token.kind {
+            let definition = Rc::new(EnumDefinition {
+                name: name.clone(),
+                variants: variants.clone(),
+            });
+
+            for (variant_name, _) in variants {
+                self.declare(variant_name, definition.variant(variant_name)?)?;
+            }
+            self.declare(name, StrawberryValue::Enum(definition))?;
+
+            Ok(StrawberryValue::Empty)
+        } else {
+            Err(StrawberryError::semantic_error("Expected an Enum token"))
+        }
+    }
+
+    fn visit_match(&mut self, token: &Token, tail: bool) -> Result<StrawberryValue, StrawberryError> {
+        if let TokenKind::Match(value_token, arms) = &token.kind {
+            let value = self.parse_token(value_token)?;
+            self.check_exhaustiveness(token, &value, arms);
+
+            for (pattern, body) in arms {
+                let mut bindings = HashMap::new();
+                if !self.match_pattern(pattern, &value, &mut bindings)? {
+                    continue;
+                }
+
+                let tokens = match &body.kind {
+                    TokenKind::BracketScope(tokens) => tokens.clone(),
+                    _ => vec![body.clone()],
+                };
+
+                let mut arm_parser = self.child(tokens, self.variables.clone());
+                for (name, value) in bindings {
+                    arm_parser.constants.remove(&name);
+                    arm_parser.variables.insert(name, value);
+                }
+                arm_parser.tail_position = tail;
+                return arm_parser.run_token_stream();
+            }
+
+            Err(StrawberryError::semantic_error(&format!(
+                "No match arm matched the value {value}"
+            )))
+        } else {
+            Err(StrawberryError::semantic_error("Expected a Match token"))
+        }
+    }
+
+    fn unit_variant(&self, name: &str) -> Option<(Rc<EnumDefinition>, usize)> {
+        match self.variables.get(name) {
+            Some(StrawberryValue::Variant(definition, index, _)) if definition.variants[*index].0 == name => {
+                Some((definition.clone(), *index))
+            }
+            _ => None,
+        }
+    }
+
+    fn is_irrefutable(&self, pattern: &Pattern) -> bool {
+        match pattern {
+            Pattern::Wildcard => true,
+            Pattern::Binding(name) => self.unit_variant(name).is_none(),
+            _ => false,
+        }
+    }
+
+    fn check_exhaustiveness(&self, token: &Token, value: &StrawberryValue, arms: &[(Pattern, Token)]) {
+        let definition = match value {
+            StrawberryValue::Variant(definition, _, _) => definition,
+            _ => return,
+        };
+
+        if arms.iter().any(|(pattern, _)| self.is_irrefutable(pattern)) {
+            return;
+        }
+
+        let missing: Vec<&str> = definition.variants.iter().map(|(name, _)| name.as_str()).filter(|name| {
+            !arms.iter().any(|(pattern, _)| match pattern {
+                Pattern::Variant(variant_name, fields) => {
+                    variant_name == name && fields.iter().all(|field| self.is_irrefutable(field))
+                }
+                Pattern::Binding(variant_name) => variant_name == name,
+                _ => false,
+            })
+        }).collect();
+
+        if !missing.is_empty() && self.warned_matches.borrow_mut().insert(token.span.start) {
+            eprintln!(
+                "Warning: non-exhaustive match on {}, missing: {}",
+                definition.name,
+                missing.join(", ")
+            );
+        }
+    }
+
+    fn match_pattern(
+        &mut self,
+        pattern: &Pattern,
+        value: &StrawberryValue,
+        bindings: &mut HashMap<String, StrawberryValue>,
+    ) -> Result<bool, StrawberryError> {
+        match pattern {
+            Pattern::Wildcard => Ok(true),
+            Pattern::Binding(name) => {
+                if let Some((definition, index)) = self.unit_variant(name) {
+                    return Ok(matches!(value, StrawberryValue::Variant(value_definition, value_index, _)
+                        if Rc::ptr_eq(&definition, value_definition) && index == *value_index));
+                }
+
+                bindings.insert(name.clone(), value.clone());
+                Ok(true)
             }
-            (ComparisonKind::LessThan, StrawberryValue::Number(lhs), StrawberryValue::Number(rhs)) => {
-                Ok(StrawberryValue::Boolean(lhs < rhs))
+            Pattern::Literal(token) => {
+                let literal = self.parse_token(token)?;
+                Ok(values_equal(&literal, value))
             }
-            (ComparisonKind::GreaterEqual, StrawberryValue::Number(lhs), StrawberryValue::Number(rhs)) => {
-                Ok(StrawberryValue::Boolean(lhs >= rhs))
+            Pattern::List(element_patterns, rest) => {
+                let values = match value {
+                    StrawberryValue::List(list) => list.borrow().clone(),
+                    _ => return Ok(false),
+                };
+
+                let length_matches = match rest {
+                    Some(_) => values.len() >= element_patterns.len(),
+                    None => values.len() == element_patterns.len(),
+                };
+                if !length_matches {
+                    return Ok(false);
+                }
+
+                for (element_pattern, element_value) in element_patterns.iter().zip(&values) {
+                    if !self.match_pattern(element_pattern, element_value, bindings)? {
+                        return Ok(false);
+                    }
+                }
+
+                if let Some(rest_name) = rest {
+                    let remaining = values[element_patterns.len()..].to_vec();
+                    bindings.insert(rest_name.clone(), StrawberryValue::List(Rc::new(RefCell::new(remaining))));
+                }
+
+                Ok(true)
             }
-            (ComparisonKind::LessEqual, StrawberryValue::Number(lhs), StrawberryValue::Number(rhs)) => {
-                Ok(StrawberryValue::Boolean(lhs <= rhs))
+            Pattern::Fields(field_patterns) => {
+                for (field, field_pattern) in field_patterns {
+                    let field_value = match value {
+                        StrawberryValue::Map(map) => map.borrow().get(field).cloned(),
+                        StrawberryValue::Record(record) => record.borrow().field(field).ok(),
+                        StrawberryValue::Variant(definition, index, values) => definition.variants[*index].1
+                            .iter()
+                            .position(|name| name == field)
+                            .map(|position| values[position].clone()),
+                        _ => None,
+                    };
+
+                    match field_value {
+                        Some(field_value) if self.match_pattern(field_pattern, &field_value, bindings)? => (),
+                        _ => return Ok(false),
+                    }
+                }
+
+                Ok(true)
             }
-            (ComparisonKind::Equal, StrawberryValue::String(lhs), StrawberryValue::String(rhs)) => {
-                Ok(StrawberryValue::Boolean(lhs == rhs))
+            Pattern::Variant(name, field_patterns) => {
+                let values = match value {
+                    StrawberryValue::Variant(definition, index, values) if definition.variants[*index].0 == *name => values.clone(),
+                    StrawberryValue::Record(record) if record.borrow().definition.name == *name => record.borrow().values.clone(),
+                    _ => return Ok(false),
+                };
+
+                if values.len() != field_patterns.len() {
+                    return Err(StrawberryError::semantic_error(&format!(
+                        "Pattern {name} expected {} fields, but the value has {}",
+                        field_patterns.len(),
+                        values.len()
+                    )));
+                }
+
+                for (field_pattern, field_value) in field_patterns.iter().zip(&values) {
+                    if !self.match_pattern(field_pattern, field_value, bindings)? {
+                        return Ok(false);
+                    }
+                }
+
+                Ok(true)
             }
-            (ComparisonKind::NotEqual, StrawberryValue::String(lhs), StrawberryValue::String(rhs)) => {
-                Ok(StrawberryValue::Boolean(lhs != rhs))
+        }
+    }
+
+    fn visit_list(&mut self, token: &Token) -> Result<StrawberryValue, StrawberryError> {
+        if let TokenKind::List(elements) = &token.kind {
+            let values: Result<Vec<_>, _> = elements.iter().map(|element| self.parse_token(element)).collect();
+            Ok(StrawberryValue::List(Rc::new(RefCell::new(values?))))
+        } else {
+            Err(StrawberryError::semantic_error("Expected a List token"))
+        }
+    }
+
+    fn visit_map(&mut self, token: &Token) -> Result<StrawberryValue, StrawberryError> {
+        if let TokenKind::Map(entries) = &token.kind {
+            let mut map = BTreeMap::new();
+            for (key, value) in entries {
+                map.insert(key.clone(), self.parse_token(value)?);
             }
-            (ComparisonKind::Equal, StrawberryValue::Boolean(lhs), StrawberryValue::Boolean(rhs)) => {
-                Ok(StrawberryValue::Boolean(lhs == rhs))
+            Ok(StrawberryValue::Map(Rc::new(RefCell::new(map))))
+        } else {
+            Err(StrawberryError::semantic_error("Expected a Map token"))
+        }
+    }
+
+    fn index_value(&mut self, target_value: StrawberryValue, index: &Token) -> Result<StrawberryValue, StrawberryError> {
+        let index_value = self.parse_token(index)?;
+
+        match (target_value, index_value) {
+            (StrawberryValue::List(list), index_value) => {
+                let list = list.borrow();
+                let position = list_position(&index_value, list.len())?;
+                Ok(list[position].clone())
             }
-            (ComparisonKind::NotEqual, StrawberryValue::Boolean(lhs), StrawberryValue::Boolean(rhs)) => {
-                Ok(StrawberryValue::Boolean(lhs != rhs))
+            (StrawberryValue::Map(map), StrawberryValue::String(key)) => map_entry(&map.borrow(), &key),
+            (StrawberryValue::Map(_), _) => Err(StrawberryError::semantic_error("Map keys must be strings")),
+            _ => Err(StrawberryError::semantic_error("Only lists and maps can be indexed")),
+        }
+    }
+
+    fn visit_coalesce(&mut self, token: &Token) -> Result<StrawberryValue, StrawberryError> {
+        if let TokenKind::Coalesce(left, right) = &token.kind {
+            match self.parse_token(left)? {
+                StrawberryValue::Nil => self.parse_token(right),
+                value => Ok(value),
             }
-            _ => Err(StrawberryError::semantic_error(
-                "Invalid comparison or unsupported types",
-            )),
+        } else {
+            Err(StrawberryError::semantic_error("Expected a Coalesce token"))
+        }
+    }
+
+    fn visit_conditional(&mut self, token: &Token, tail: bool) -> Result<StrawberryValue, StrawberryError> {
+        if let TokenKind::Conditional(condition, then_branch, else_branch) = &token.kind {
+            let branch = match self.parse_token(condition)? {
+                StrawberryValue::Boolean(true) => then_branch,
+                StrawberryValue::Boolean(false) => else_branch,
+                value => {
+                    return Err(StrawberryError::semantic_error(&format!(
+                        "Condition of '?' must be a boolean, but got a {}",
+                        value.type_name()
+                    )))
+                }
+            };
+
+            if tail {
+                self.parse_tail_token(branch)
+            } else {
+                self.parse_token(branch)
+            }
+        } else {
+            Err(StrawberryError::semantic_error("Expected a Conditional token"))
         }
     }
 
+    fn visit_assignment(&mut self, token: &Token) -> Result<StrawberryValue, StrawberryError> {
+        if let TokenKind::Assignment(target, value) = &token.kind {
+            let assigned_value = self.parse_token(value)?;
+
+            match &target.kind {
+                TokenKind::Index(target, index) => {
+                    let target_value = self.parse_token(target)?;
+                    let index_value = self.parse_token(index)?;
+
+                    match (target_value, index_value) {
+                        (StrawberryValue::List(list), index_value) => {
+                            let mut list = list.borrow_mut();
+                            let position = list_position(&index_value, list.len())?;
+                            list[position] = assigned_value.clone();
+                        }
+                        (StrawberryValue::Map(map), StrawberryValue::String(key)) => {
+                            map.borrow_mut().insert(key, assigned_value.clone());
+                        }
+                        (StrawberryValue::Map(_), _) => return Err(StrawberryError::semantic_error("Map keys must be strings")),
+                        _ => return Err(StrawberryError::semantic_error("Only lists and maps can be indexed")),
+                    }
+                }
+                TokenKind::Member(target, name) => {
+                    match self.parse_token(target)? {
+                        StrawberryValue::Map(map) => {
+                            map.borrow_mut().insert(name.clone(), assigned_value.clone());
+                        }
+                        StrawberryValue::Record(record) => {
+                            record.borrow_mut().set_field(name, assigned_value.clone())?;
+                        }
+                        _ => return Err(StrawberryError::semantic_error(&format!("Cannot assign '{name}' on a value that is not a map or an object"))),
+                    }
+                }
+                _ => return Err(StrawberryError::semantic_error("Invalid assignment target")),
+            }
+
+            Ok(assigned_value)
+        } else {
+            Err(StrawberryError::semantic_error("Expected an Assignment token"))
+        }
+    }
+
+    fn visit_return(&mut self, token: &Token) -> Result<StrawberryValue, StrawberryError> {
+        if let TokenKind::Return(value) = &token.kind {
+            if !self.in_function {
+                return Err(StrawberryError::semantic_error("'return' can only be used inside a function"));
+            }
+
+            let returned_value = if let Some(value_token) = value {
+                self.parse_tail_token(value_token)?
+            } else {
+                StrawberryValue::Empty
+            };
+
+            Err(StrawberryError::return_value(returned_value))
+        } else {
+            Err(StrawberryError::semantic_error("Expected a Return token"))
+        }
+    }
+
+    fn evaluate_comparison(
+        &self,
+        operator: ComparisonKind,
+        left: StrawberryValue,
+        right: StrawberryValue,
+    ) -> Result<StrawberryValue, StrawberryError> {
+        let ordering = match (&operator, &left, &right) {
+            (ComparisonKind::Equal, _, _) => return Ok(StrawberryValue::Boolean(values_equal(&left, &right))),
+            (ComparisonKind::NotEqual, _, _) => return Ok(StrawberryValue::Boolean(!values_equal(&left, &right))),
+            (_, StrawberryValue::Number(lhs), StrawberryValue::Number(rhs)) => lhs.partial_cmp(rhs),
+            (_, StrawberryValue::String(lhs), StrawberryValue::String(rhs)) => Some(lhs.cmp(rhs)),
+            _ => {
+                return Err(StrawberryError::semantic_error(&format!(
+                    "Cannot order a {} and a {}; only numbers and strings can be ordered",
+                    left.type_name(),
+                    right.type_name()
+                )))
+            }
+        };
+
+        let result = match (operator, ordering) {
+            (_, None) => false,
+            (ComparisonKind::GreaterThan, Some(ordering)) => ordering.is_gt(),
+            (ComparisonKind::LessThan, Some(ordering)) => ordering.is_lt(),
+            (ComparisonKind::GreaterEqual, Some(ordering)) => ordering.is_ge(),
+            (ComparisonKind::LessEqual, Some(ordering)) => ordering.is_le(),
+            (ComparisonKind::Equal | ComparisonKind::NotEqual, Some(_)) => unreachable!(),
+        };
+
+        Ok(StrawberryValue::Boolean(result))
+    }
+
     fn parse_token(&mut self, token: &Token) -> Result<StrawberryValue, StrawberryError> {
         match &token.kind {
             TokenKind::Boolean(value) => Ok(StrawberryValue::Boolean(*value)),
-            TokenKind::BracketScope(value) => Ok(StrawberryValue::Block(value.iter().map(|t| Box::new(t.clone())).collect())),
+            TokenKind::BracketScope(value) => Ok(StrawberryValue::Block(value.clone())),
             TokenKind::LiteralString(_) 
             | TokenKind::Number(_) 
             | TokenKind::Expression(_, _, _) => self.visit_expression(token),
     
             TokenKind::Let(_, _) => self.visit_let(token),
+
+            TokenKind::Const(_, _) => self.visit_const(token),
     
             TokenKind::Identifier(_) => self.visit_identifier(token),
     
             TokenKind::Function(_, _, _) => self.visit_function(token),
+
+            TokenKind::Import(_, _)
+            | TokenKind::ImportNames(_, _) => self.visit_import(token),
+
+            TokenKind::Export(_) => self.visit_export(token),
+
+            TokenKind::Struct(_, _) => self.visit_struct(token),
+
+            TokenKind::Class(_, _, _) => self.visit_class(token),
+
+            TokenKind::Enum(_, _) => self.visit_enum(token),
+
+            TokenKind::Match(_, _) => self.visit_match(token, false),
     
-            TokenKind::Call(_, _) => self.visit_call(token),
+            TokenKind::Call(_, _)
+            | TokenKind::MethodCall(_, _, _)
+            | TokenKind::OptionalMethodCall(_, _, _) => self.visit_postfix(token),
+
+            TokenKind::Return(_) => self.visit_return(token),
+
+            TokenKind::List(_) => self.visit_list(token),
+
+            TokenKind::Index(_, _) => self.visit_postfix(token),
+
+            TokenKind::Map(_) => self.visit_map(token),
+
+            TokenKind::Member(_, _)
+            | TokenKind::OptionalMember(_, _) => self.visit_postfix(token),
+
+            TokenKind::Coalesce(_, _) => self.visit_coalesce(token),
+
+            TokenKind::Conditional(_, _, _) => self.visit_conditional(token, false),
+
+            TokenKind::Nil => Ok(StrawberryValue::Nil),
+
+            TokenKind::Assignment(_, _) => self.visit_assignment(token),
     
             TokenKind::Comparison(operator, left, right) => {
                 let left_value = self.parse_token(left)?;
@@ -251,10 +1380,185 @@ impl StrawberryParser {
 
     pub fn run_token_stream(&mut self) -> Result<StrawberryValue, StrawberryError> {
         let mut last_result = StrawberryValue::Empty;
-        for token in self.tokens.clone() {
-            last_result = self.parse_token(&token)?;
+        let tokens = self.tokens.clone();
+
+        for token in tokens.iter().filter(|token| is_function_declaration(token)) {
+            self.parse_token(token)?;
+        }
+
+        for (index, token) in tokens.iter().enumerate() {
+            if is_function_declaration(token) {
+                last_result = StrawberryValue::Empty;
+                continue;
+            }
+
+            last_result = if self.tail_position && index == tokens.len() - 1 {
+                self.parse_tail_token(token)?
+            } else {
+                self.parse_token(token)?
+            };
         }
 
         Ok(last_result)
     }
 }
+
+
+fn is_function_declaration(token: &Token) -> bool {
+    match &token.kind {
+        TokenKind::Function(_, _, _) => true,
+        TokenKind::Export(declaration) => matches!(declaration.kind, TokenKind::Function(_, _, _)),
+        _ => false,
+    }
+}
+
+pub fn list_position(index: &StrawberryValue, length: usize) -> Result<usize, StrawberryError> {
+    let number = match index {
+        StrawberryValue::Number(number) => number,
+        _ => return Err(StrawberryError::semantic_error("List index must be a number"))
+    };
+
+    if number.fract() != 0.0 {
+        return Err(StrawberryError::semantic_error(&format!("List index must be an integer, got {number}")));
+    }
+
+    let position = if *number < 0.0 { length as f64 + number } else { *number };
+
+    if position < 0.0 || position >= length as f64 {
+        return Err(StrawberryError::semantic_error(&format!(
+            "Index {number} out of bounds for list of length {length}"
+        )));
+    }
+
+    Ok(position as usize)
+}
+
+pub fn map_entry(map: &BTreeMap<String, StrawberryValue>, key: &str) -> Result<StrawberryValue, StrawberryError> {
+    map.get(key).cloned().ok_or_else(|| {
+        StrawberryError::semantic_error(&format!("Key '{key}' not found in map"))
+    })
+}
+
+pub fn values_equal(left: &StrawberryValue, right: &StrawberryValue) -> bool {
+    compare_values(left, right, &mut Vec::new())
+}
+
+fn compare_values(left: &StrawberryValue, right: &StrawberryValue, comparing: &mut Vec<(*const (), *const ())>) -> bool {
+    let pointers = match (left, right) {
+        (StrawberryValue::List(lhs), StrawberryValue::List(rhs)) => Some((Rc::as_ptr(lhs) as *const (), Rc::as_ptr(rhs) as *const ())),
+        (StrawberryValue::Map(lhs), StrawberryValue::Map(rhs)) => Some((Rc::as_ptr(lhs) as *const (), Rc::as_ptr(rhs) as *const ())),
+        (StrawberryValue::Record(lhs), StrawberryValue::Record(rhs)) => Some((Rc::as_ptr(lhs) as *const (), Rc::as_ptr(rhs) as *const ())),
+        _ => None,
+    };
+
+    if let Some(pointers) = pointers {
+        if comparing.contains(&pointers) {
+            return true;
+        }
+        comparing.push(pointers);
+        let equal = compare_children(left, right, comparing);
+        comparing.pop();
+        return equal;
+    }
+
+    compare_children(left, right, comparing)
+}
+
+fn compare_children(left: &StrawberryValue, right: &StrawberryValue, comparing: &mut Vec<(*const (), *const ())>) -> bool {
+    match (left, right) {
+        (StrawberryValue::Number(lhs), StrawberryValue::Number(rhs)) => lhs == rhs,
+        (StrawberryValue::String(lhs), StrawberryValue::String(rhs)) => lhs == rhs,
+        (StrawberryValue::Boolean(lhs), StrawberryValue::Boolean(rhs)) => lhs == rhs,
+        (StrawberryValue::Nil, StrawberryValue::Nil) => true,
+        (StrawberryValue::Empty, StrawberryValue::Empty) => true,
+        (StrawberryValue::List(lhs), StrawberryValue::List(rhs)) => {
+            if Rc::ptr_eq(lhs, rhs) {
+                return true;
+            }
+            let (lhs, rhs) = (lhs.borrow(), rhs.borrow());
+            lhs.len() == rhs.len() && lhs.iter().zip(rhs.iter()).all(|(lhs, rhs)| compare_values(lhs, rhs, comparing))
+        }
+        (StrawberryValue::Map(lhs), StrawberryValue::Map(rhs)) => {
+            if Rc::ptr_eq(lhs, rhs) {
+                return true;
+            }
+            let (lhs, rhs) = (lhs.borrow(), rhs.borrow());
+            lhs.len() == rhs.len()
+                && lhs.iter().zip(rhs.iter()).all(|((left_key, lhs), (right_key, rhs))| {
+                    left_key == right_key && compare_values(lhs, rhs, comparing)
+                })
+        }
+        (StrawberryValue::Record(lhs), StrawberryValue::Record(rhs)) => {
+            if Rc::ptr_eq(lhs, rhs) {
+                return true;
+            }
+            let (lhs, rhs) = (lhs.borrow(), rhs.borrow());
+            Rc::ptr_eq(&lhs.definition, &rhs.definition)
+                && lhs.values.iter().zip(rhs.values.iter()).all(|(lhs, rhs)| compare_values(lhs, rhs, comparing))
+        }
+        (StrawberryValue::Struct(lhs), StrawberryValue::Struct(rhs)) => Rc::ptr_eq(lhs, rhs),
+        (StrawberryValue::Enum(lhs), StrawberryValue::Enum(rhs)) => Rc::ptr_eq(lhs, rhs),
+        (StrawberryValue::Module(lhs), StrawberryValue::Module(rhs)) => Rc::ptr_eq(lhs, rhs),
+        (
+            StrawberryValue::VariantConstructor(left_definition, left_index),
+            StrawberryValue::VariantConstructor(right_definition, right_index),
+        ) => Rc::ptr_eq(left_definition, right_definition) && left_index == right_index,
+        (StrawberryValue::NativeFunction(lhs, _), StrawberryValue::NativeFunction(rhs, _)) => lhs == rhs,
+        (
+            StrawberryValue::Function(left_name, left_params, left_body, _),
+            StrawberryValue::Function(right_name, right_params, right_body, _),
+        ) => left_name == right_name && left_params == right_params && left_body == right_body,
+        (
+            StrawberryValue::Variant(left_definition, left_index, left_values),
+            StrawberryValue::Variant(right_definition, right_index, right_values),
+        ) => {
+            Rc::ptr_eq(left_definition, right_definition)
+                && left_index == right_index
+                && left_values.iter().zip(right_values).all(|(lhs, rhs)| compare_values(lhs, rhs, comparing))
+        }
+        _ => false,
+    }
+}
+
+pub fn module_export(module: &Module, name: &str) -> Result<StrawberryValue, StrawberryError> {
+    module.exports.get(name).cloned().ok_or_else(|| {
+        StrawberryError::semantic_error(&format!("Module '{}' does not export '{name}'", module.name))
+    })
+}
+
+fn binding_names(pattern: &Pattern, names: &mut Vec<String>) {
+    match pattern {
+        Pattern::Binding(name) => names.push(name.clone()),
+        Pattern::Variant(_, patterns) => patterns.iter().for_each(|pattern| binding_names(pattern, names)),
+        Pattern::List(patterns, rest) => {
+            patterns.iter().for_each(|pattern| binding_names(pattern, names));
+            names.extend(rest.clone());
+        }
+        Pattern::Fields(fields) => fields.iter().for_each(|(_, pattern)| binding_names(pattern, names)),
+        Pattern::Wildcard | Pattern::Literal(_) => {}
+    }
+}
+
+pub fn member_of(value: StrawberryValue, name: &str) -> Result<StrawberryValue, StrawberryError> {
+    match value {
+        StrawberryValue::Map(map) => map_entry(&map.borrow(), name),
+        StrawberryValue::Record(record) => {
+            let record = record.borrow();
+            match record.definition.methods.get(name) {
+                Some(method) => Ok(method.clone()),
+                None => record.field(name),
+            }
+        }
+        StrawberryValue::Enum(definition) => definition.variant(name),
+        StrawberryValue::Module(module) => module_export(&module, name),
+        StrawberryValue::Variant(definition, index, values) => {
+            let (variant_name, fields) = &definition.variants[index];
+            match fields.iter().position(|field| field == name) {
+                Some(position) => Ok(values[position].clone()),
+                None => Err(StrawberryError::semantic_error(&format!("{variant_name} has no field '{name}'"))),
+            }
+        }
+        StrawberryValue::Nil => Err(StrawberryError::semantic_error(&format!("Cannot access '{name}' on nil; use '?.' instead"))),
+        _ => Err(StrawberryError::semantic_error(&format!("Cannot access '{name}' on a value that is not a map or an object"))),
+    }
+}
\ No newline at end of file
@@ -0,0 +1,286 @@
+use std::{fs, path::PathBuf, process::Command, sync::atomic::{AtomicUsize, Ordering}};
+
+static NEXT_DIRECTORY: AtomicUsize = AtomicUsize::new(0);
+
+struct Run {
+    output: String,
+    code: Option<i32>
+}
+
+// Every run gets its own directory, so scripts can import modules next to them
+fn run_files(files: &[(&str, &str)], options: &[&str]) -> Run {
+    let directory: PathBuf = std::env::temp_dir().join(format!(
+        "strawberry-test-{}-{}",
+        std::process::id(),
+        NEXT_DIRECTORY.fetch_add(1, Ordering::SeqCst)
+    ));
+
+    for (name, source) in files {
+        let path = directory.join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, source).unwrap();
+    }
+
+    let output = Command::new(env!("CARGO_BIN_EXE_StrawberryRust"))
+        .args(options)
+        .arg(directory.join("main.sb"))
+        .output()
+        .unwrap();
+    fs::remove_dir_all(&directory).unwrap();
+
+    Run {
+        output: String::from_utf8_lossy(&output.stdout).trim_end().to_string(),
+        code: output.status.code()
+    }
+}
+
+fn run_with(source: &str, options: &[&str]) -> String {
+    let run = run_files(&[("main.sb", source)], options);
+    assert_eq!(run.code, Some(0), "interpreter failed with output {:?}", run.output);
+    run.output
+}
+
+fn run(source: &str) -> String {
+    run_with(source, &[])
+}
+
+#[test]
+fn return_leaves_the_function_early() {
+    let source = "
+        function sign(n) {
+            if(n < 0, { return -1; })
+            if(n == 0, { return 0; })
+            1
+        }
+        function nothing() { return; }
+        strawberry(sign(-5), sign(0), sign(3), nothing())
+    ";
+    assert_eq!(run(source), "-1 0 1 (Empty)");
+}
+
+#[test]
+fn return_outside_of_a_function_is_an_error() {
+    assert_eq!(run("return 1;"), "Semantic error: 'return' can only be used inside a function");
+}
+
+#[test]
+fn return_with_several_values_is_an_error() {
+    let source = "
+        function f() { return 1 2; }
+        strawberry(f())
+    ";
+    assert_eq!(run(source), "Syntax error: Return statement was expecting a single value");
+}
+
+#[test]
+fn return_in_a_module_imported_from_a_function_is_an_error() {
+    let run = run_files(&[
+        ("main.sb", "
+            function f() {
+                import 'mods/ret.sb' as r
+                'normal'
+            }
+            strawberry(f())
+        "),
+        ("mods/ret.sb", "return 'leaked';")
+    ], &[]);
+    assert_eq!(run.output, "Semantic error: 'return' can only be used inside a function");
+}
+
+#[test]
+fn deep_recursion_reports_a_stack_overflow() {
+    let source = "
+        function f(n) { f(n + 1) + 1 }
+        f(0)
+    ";
+    assert_eq!(
+        run(source),
+        "Stack overflow: maximum call depth of 1000 exceeded\nCall chain: f (x1001)"
+    );
+}
+
+#[test]
+fn stack_overflow_collapses_mutual_recursion() {
+    let source = "
+        function a(n) { b(n) + 1 }
+        function b(n) { a(n) + 1 }
+        strawberry(a(1))
+    ";
+    assert_eq!(
+        run_with(source, &["--max-call-depth", "10"]),
+        "Stack overflow: maximum call depth of 10 exceeded\nCall chain: (a -> b) (x5) -> a"
+    );
+}
+
+#[test]
+fn maximum_call_depth_limit_is_reachable() {
+    let source = "
+        function f(n) {
+            if(n == 0, { 0 }, { f(n - 1) + 1 })
+        }
+        strawberry(f(16383))
+    ";
+    assert_eq!(run_with(source, &["--max-call-depth", "16384"]), "16383");
+}
+
+#[test]
+fn call_depth_above_the_limit_is_rejected() {
+    assert_eq!(
+        run_with("strawberry(1)", &["--max-call-depth", "100000"]),
+        "Invalid value for --max-call-depth: \"100000\" exceeds the maximum of 16384"
+    );
+}
+
+#[test]
+fn tail_calls_run_in_constant_stack() {
+    let source = "
+        function count(n, total) {
+            if(n == 0, { total }, { count(n - 1, total + 1) })
+        }
+        strawberry(count(100000, 0))
+    ";
+    assert_eq!(run(source), "100000");
+}
+
+#[test]
+fn list_index_out_of_bounds_is_an_error() {
+    let source = "
+        let xs = [1, 2];
+        strawberry(xs[-1])
+        strawberry(xs[5])
+    ";
+    assert_eq!(run(source), "2\nSemantic error: Index 5 out of bounds for list of length 2");
+}
+
+#[test]
+fn self_referencing_collections_print_and_compare() {
+    let source = "
+        import list
+        let xs = [1];
+        list.push(xs, xs)
+        let ys = [1];
+        list.push(ys, ys)
+        let m = { a: 1 };
+        m['self'] = m;
+        strawberry(xs, m, xs == ys, xs == [1, 2])
+    ";
+    assert_eq!(run(source), "[1, [...]] { a: 1, self: {...} } true false");
+}
+
+#[test]
+fn constants_cannot_be_reassigned() {
+    let source = "
+        const x = 1;
+        let x = 2;
+    ";
+    assert_eq!(run(source), "Semantic error: Cannot reassign constant 'x'");
+}
+
+#[test]
+fn destructuring_mismatch_is_an_error() {
+    assert_eq!(
+        run("let [a, b] = [1];"),
+        "Semantic error: The value [1] does not match the pattern of the let statement"
+    );
+}
+
+#[test]
+fn named_arguments_bind_by_name() {
+    let source = "
+        function f(a, b) { a - b }
+        strawberry(f(b: 1, a: 3))
+    ";
+    assert_eq!(run(source), "2");
+}
+
+#[test]
+fn unknown_named_argument_is_an_error() {
+    let source = "
+        function f(a, b) { a + b }
+        strawberry(f(1, c: 2))
+    ";
+    assert_eq!(run(source), "Semantic error: Function f has no parameter named 'c'");
+}
+
+#[test]
+fn named_arguments_to_native_functions_are_an_error() {
+    assert_eq!(run("strawberry(a: 1)"), "Semantic error: strawberry does not accept named arguments");
+}
+
+#[test]
+fn import_cycles_are_reported() {
+    let run = run_files(&[
+        ("main.sb", "import 'mods/a.sb' as a"),
+        ("mods/a.sb", "import 'b.sb' as b"),
+        ("mods/b.sb", "import 'a.sb' as a")
+    ], &[]);
+    assert_eq!(run.output, "Semantic error: Import cycle detected: a.sb -> b.sb -> a.sb");
+}
+
+#[test]
+fn optional_chaining_skips_the_rest_of_the_chain() {
+    let source = "
+        let m = { f: nil, g: { h: [1, 2] } };
+        strawberry(m.f?.g.h, m.f?.g.h[0], m.f?.g.run(), nil?.x, m.g?.h[1])
+    ";
+    assert_eq!(run(source), "nil nil nil nil 2");
+}
+
+#[test]
+fn conversions_live_in_the_types_module() {
+    assert_eq!(run("strawberry(type_of(1))"), "Semantic error: Undefined variable: type_of");
+    assert_eq!(run("import types\nstrawberry(types.type_of(1), types.to_number('2'))"), "number 2");
+}
+
+#[test]
+fn map_len_only_accepts_maps() {
+    let source = "
+        import map
+        strawberry(map.len({ a: 1, b: 2 }))
+        strawberry(map.len('abc'))
+    ";
+    assert_eq!(run(source), "2\nSemantic error: First argument of 'len' must be a map");
+}
+
+#[test]
+fn huge_strings_are_rejected() {
+    let source = "
+        import string
+        strawberry(string.repeat('ab', 1000000000000000000))
+    ";
+    assert_eq!(run(source), "Semantic error: Result of 'repeat' would be longer than 1073741824 bytes");
+
+    let source = "
+        import string
+        strawberry(string.pad_left('x', 9000000000000000000))
+    ";
+    assert_eq!(run(source), "Semantic error: Result of 'pad_left' would be longer than 1073741824 bytes");
+}
+
+#[test]
+fn clamp_rejects_nan_bounds() {
+    let source = "
+        import math
+        strawberry(math.clamp(1, math.acos(2), 2))
+    ";
+    assert_eq!(run(source), "Semantic error: Bounds of 'clamp' must not be NaN");
+}
+
+#[test]
+fn sleep_rejects_durations_that_are_too_long() {
+    let source = "
+        import time
+        time.sleep(100000000000000000000)
+    ";
+    assert_eq!(
+        run(source),
+        "Semantic error: Argument of 'sleep' must be a non-negative, representable number of seconds"
+    );
+}
+
+#[test]
+fn negative_seeds_match_between_the_option_and_the_module() {
+    let seeded = run("import random\nrandom.seed(-1)\nstrawberry(random.int(1, 1000000))");
+    let from_option = run_with("import random\nstrawberry(random.int(1, 1000000))", &["--seed", "-1"]);
+    assert_eq!(seeded, from_option);
+}
\ No newline at end of file